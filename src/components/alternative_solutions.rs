@@ -0,0 +1,106 @@
+use crate::models::board::{Board as BoardModel, FieldCell};
+use crate::models::grid::Grid;
+use crate::models::solver::Solutions;
+use crate::models::uniqueness::alternative_solutions;
+
+use yew::prelude::*;
+
+/// Other solutions to list. The search for them shares one `SEARCH_BUDGET`, so
+/// asking for more rarely finds more before it gives up
+const MAX_SOLUTIONS: usize = 10;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct AlternativeSolutionsProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+    /// Solution shown on the board next to the intended picture
+    pub alternative: UseStateHandle<Option<Grid<FieldCell>>>,
+}
+
+/// Flip through other solutions of an ambiguous puzzle
+#[function_component(AlternativeSolutions)]
+pub fn alternative_solutions_panel(props: &AlternativeSolutionsProps) -> Html {
+    let solutions = use_state(|| Option::<Solutions>::None);
+    let index = use_state(|| 0);
+
+    {
+        // solutions found for an earlier drawing are of no use any more
+        let (solutions, alternative) = (solutions.clone(), props.alternative.clone());
+        use_effect_with_deps(
+            move |_| {
+                solutions.set(None);
+                alternative.set(None);
+                || ()
+            },
+            (*props.puzzle).clone(),
+        );
+    }
+
+    let onsearch = {
+        let (solutions, index) = (solutions.clone(), index.clone());
+        let (puzzle, alternative) = (props.puzzle.clone(), props.alternative.clone());
+        Callback::from(move |_| {
+            let board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            let found =
+                alternative_solutions(board.solution_ref(), board.givens_ref(), MAX_SOLUTIONS);
+            log::info!("Found {} other solutions", found.found.len());
+            alternative.set(found.found.first().cloned());
+            index.set(0);
+            solutions.set(Some(found));
+        })
+    };
+    let onhide = {
+        let (solutions, alternative) = (solutions.clone(), props.alternative.clone());
+        Callback::from(move |_| {
+            solutions.set(None);
+            alternative.set(None);
+        })
+    };
+
+    let browser = match &*solutions {
+        None => html!(),
+        Some(Solutions { found, gave_up }) if found.is_empty() => {
+            if *gave_up {
+                html! { <p>{"The search gave up before finding other solutions."}</p> }
+            } else {
+                html! { <p>{"There are no other solutions."}</p> }
+            }
+        }
+        Some(Solutions { found, gave_up }) => {
+            let step = |forward: bool| {
+                let found = found.clone();
+                let idx = if forward {
+                    (*index + 1) % found.len()
+                } else {
+                    (*index + found.len() - 1) % found.len()
+                };
+                let (index, alternative) = (index.clone(), props.alternative.clone());
+                Callback::from(move |_| {
+                    alternative.set(found.get(idx).cloned());
+                    index.set(idx);
+                })
+            };
+            let more = if found.len() == MAX_SOLUTIONS || *gave_up {
+                " or more"
+            } else {
+                ""
+            };
+            html! {
+                <p>
+                    <button onclick={step(false)}>{"‹"}</button>
+                    {format!(" Other solution {} of {}{more} ", *index + 1, found.len())}
+                    <button onclick={step(true)}>{"›"}</button>
+                    <button onclick={onhide} style="margin-left: 4px">{"Hide"}</button>
+                    <br/>
+                    {"Highlighted cells differ from the picture"}
+                </p>
+            }
+        }
+    };
+
+    html! {
+        <>
+            <button onclick={onsearch} style="margin-left: 4px">{"Other solutions"}</button>
+            {browser}
+        </>
+    }
+}
@@ -1,17 +1,46 @@
+mod challenge;
+mod details;
 mod dragselection;
+mod explanation;
+mod export;
+mod playback;
 mod preview;
+mod selection;
+mod stats;
 
 use crate::{
-    models::board::{Board as BoardModel, FieldCell},
+    components::copy_to_clipboard::copy_text,
+    components::library,
+    components::underlay::Underlay,
+    models::ascii_art::TextFormat,
+    models::board::{Board as BoardModel, FieldCell, Progress},
+    models::clues::{Clues, Line},
+    models::explanation::{explain_cell, Explanation},
+    models::grid::Grid,
+    models::shapes::{Ellipse, FloodFill, Path, RectOutline, StraightLine},
+    models::solver::{contradicting_lines, deduction_passes, next_deduction, Contradiction},
+    models::symmetry::Symmetry,
     routes::Route,
+    storage,
 };
+use challenge::Challenge;
+use details::details_header;
 use dragselection::DragSelection;
-use preview::NonogramPreview;
+use explanation::{explained_cell_svg, explanation_panel};
+pub use export::{download_text, ExportFormat, SheetContent};
+use playback::{Playback, PlaybackMsg};
+pub use preview::NonogramPreview;
+use selection::{block_svg, Selection};
+pub use stats::{best_record, forget_puzzle, solve_status, SolveStatus};
+use stats::{tab_hidden, SolveStats, VisibilityListener};
 
-use itertools::iproduct;
+use itertools::{iproduct, Itertools};
+use web_sys::{Element, HtmlInputElement};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
+const AUTO_MARK_KEY: &str = "auto-mark";
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LeftRight {
     Left,
@@ -23,31 +52,149 @@ struct Drag {
     start: (i32, i32),
     end: (i32, i32),
     button: LeftRight,
+    /// Every cell the pointer passed over, in order
+    path: Vec<(i32, i32)>,
 }
 
 pub struct Board {
     board: BoardModel,
     mode: BoardMode,
+    tool: DrawingTool,
+    symmetry: Symmetry,
     puzzle_code: String,
+    /// Library entry of the puzzle in Set mode, once known
+    draft: Option<u32>,
     drag: Option<Drag>,
+    /// Block of the solution chosen with the select tool
+    selection: Option<Selection>,
+    clipboard: Option<Grid<FieldCell>>,
+    /// Clipboard is about to be pasted at the cursor
+    pasting: bool,
+    cursor: Option<(i32, i32)>,
+    export: Option<(SheetContent, ExportFormat)>,
+    export_ref: NodeRef,
+    /// Reasoning behind a cell the player asked about in Solve mode
+    explanation: Option<((usize, usize), Explanation)>,
+    playback: Playback,
+    /// Mark the rest of a line once its fills match the clue
+    auto_mark: bool,
+    /// Progress before each change in Solve mode, most recent last
+    history: Vec<Progress>,
+    challenge: Option<Challenge>,
+    /// Time, moves and mistakes of the current attempt in Solve mode
+    stats: SolveStats,
+    /// The field matched the solution when last rendered
+    solved: bool,
+    /// Only kept so the listener stays active as long as the board
+    _visibility_listener: Option<VisibilityListener>,
 }
 
 pub enum BoardMsg {
     CompleteDragSelection(i32, i32, LeftRight),
     UpdateDragSelection(i32, i32, LeftRight),
+    Export(SheetContent, ExportFormat),
+    /// Pointer moved to a cell without a button pressed
+    Hover(i32, i32),
+    Copy,
+    Cut,
+    Paste,
+    Deselect,
+    /// Show why a cell is filled or empty
+    Explain(i32, i32),
+    Playback(PlaybackMsg),
+    AutoMark(bool),
+    Undo,
+    /// Enter what-if mode
+    StartSandbox,
+    /// Leave what-if mode, keeping or dropping the cells tried out
+    CommitSandbox,
+    DiscardSandbox,
+    /// Start the challenge over after losing all lives
+    RetryChallenge,
+    /// Time to update the time shown
+    TimerTick,
+    /// The tab was hidden or shown again
+    VisibilityChange,
 }
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum BoardMode {
     Solve,
     Set,
+    /// Set by entering clues, the board only shows the solution found
+    Clues,
 }
 
 impl yew::html::ImplicitClone for BoardMode {}
 
+/// How a drag over the board sets cells in Set mode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrawingTool {
+    /// Fill the whole rectangle spanned by the drag
+    Rectangle,
+    /// Draw freehand along the path of the pointer
+    Pencil,
+    Line,
+    RectOutline,
+    Ellipse,
+    /// Set the area of equal cells around the starting cell
+    FloodFill,
+    /// Choose a block to copy, cut or move, or where to paste
+    Select,
+    /// Reveal cells at the start of solving, or hide them again
+    Given,
+}
+
+impl DrawingTool {
+    pub const ALL: [DrawingTool; 8] = [
+        DrawingTool::Rectangle,
+        DrawingTool::Pencil,
+        DrawingTool::Line,
+        DrawingTool::RectOutline,
+        DrawingTool::Ellipse,
+        DrawingTool::FloodFill,
+        DrawingTool::Select,
+        DrawingTool::Given,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DrawingTool::Rectangle => "Rectangle",
+            DrawingTool::Pencil => "Pencil",
+            DrawingTool::Line => "Line",
+            DrawingTool::RectOutline => "Outline",
+            DrawingTool::Ellipse => "Ellipse",
+            DrawingTool::FloodFill => "Fill area",
+            DrawingTool::Select => "Select",
+            DrawingTool::Given => "Givens",
+        }
+    }
+}
+
+impl yew::html::ImplicitClone for DrawingTool {}
+
 #[derive(PartialEq, Properties)]
 pub struct BoardProps {
     pub mode: BoardMode,
+    #[prop_or(DrawingTool::Rectangle)]
+    pub tool: DrawingTool,
+    #[prop_or(Symmetry::None)]
+    pub symmetry: Symmetry,
+    /// Reference image shown behind the field in Set mode
+    #[prop_or_default]
+    pub underlay: Option<Underlay>,
+    /// Colour cells in Set mode by how the line solver determines them
+    #[prop_or(false)]
+    pub heatmap: bool,
+    /// Another solution of the clues, compared with the picture in Set mode
+    #[prop_or_default]
+    pub alternative: Option<Grid<FieldCell>>,
+    /// Wrong fills allowed in Solve mode, any number if None
+    #[prop_or_default]
+    pub lives: Option<u32>,
+    /// Library entry that follows the puzzle as it is edited in Set mode
+    #[prop_or_default]
+    pub draft: Option<u32>,
     pub puzzle: UseStateHandle<AttrValue>,
 }
 
@@ -56,15 +203,39 @@ impl Component for Board {
     type Properties = BoardProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        Self {
+        let mut board = Self {
             board: match &ctx.props().puzzle.as_ref() {
                 &"" => BoardModel::new(),
                 puzzle => BoardModel::from_serialized_solution(puzzle),
             },
             mode: ctx.props().mode,
+            tool: ctx.props().tool,
+            symmetry: ctx.props().symmetry,
             puzzle_code: ctx.props().puzzle.to_string(),
+            draft: ctx.props().draft,
             drag: None,
-        }
+            selection: None,
+            clipboard: None,
+            pasting: false,
+            cursor: None,
+            export: None,
+            export_ref: NodeRef::default(),
+            explanation: None,
+            playback: Playback::default(),
+            auto_mark: storage::load(AUTO_MARK_KEY).as_deref() == Some("true"),
+            history: vec![],
+            challenge: ctx
+                .props()
+                .lives
+                .map(|lives| Challenge::load(lives, ctx.props().puzzle.as_ref())),
+            stats: SolveStats::default(),
+            solved: false,
+            _visibility_listener: VisibilityListener::new(ctx.link()),
+        };
+        board.load_progress();
+        board.load_stats();
+        board.list_played();
+        board
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -73,20 +244,128 @@ impl Component for Board {
                 self.update_drag_selection(row, col, btn)
             }
             BoardMsg::CompleteDragSelection(row, col, btn) => {
-                self.complete_drag_selection(self.mode, row, col, btn)
+                // a drag that changes no cells still ends its preview or selection,
+                // so redraw, but skip storing the unchanged puzzle and route
+                if !self.complete_drag_selection(self.mode, row, col, btn) {
+                    return true;
+                }
+                true
+            }
+            BoardMsg::Export(content, format) => {
+                self.export = Some((content, format));
+                return true;
+            }
+            BoardMsg::Hover(row, col) => {
+                self.cursor = Some((row, col));
+                return true;
+            }
+            BoardMsg::Copy => {
+                self.copy_selection();
+                return true;
+            }
+            BoardMsg::Cut => {
+                self.copy_selection();
+                let selection = self.selection.take();
+                selection.is_some_and(|selection| {
+                    self.board.clear_region(selection.rows(), selection.cols())
+                })
+            }
+            BoardMsg::Paste => {
+                self.pasting = self.clipboard.is_some();
+                return true;
+            }
+            BoardMsg::Deselect => {
+                self.selection = None;
+                self.pasting = false;
+                return true;
+            }
+            BoardMsg::Explain(row, col) => {
+                let cell = (row as usize, col as usize);
+                let clues = Clues::from_solution(self.board.solution_ref());
+                let field = self.board.field_with_sandbox();
+                let explanation = explain_cell(&clues, &field, cell);
+                self.explanation = Some((cell, explanation));
+                return true;
+            }
+            BoardMsg::Playback(msg) => {
+                self.update_playback(ctx, msg);
+                return true;
+            }
+            BoardMsg::AutoMark(auto_mark) => {
+                self.auto_mark = auto_mark;
+                storage::store(AUTO_MARK_KEY, &auto_mark.to_string());
+                return true;
+            }
+            BoardMsg::Undo => {
+                if let Some(progress) = self.history.pop() {
+                    self.board.restore_progress(&progress);
+                    self.store_progress();
+                    self.stats.record.moves += 1;
+                    self.stats.store(&self.puzzle_key("stats"));
+                }
+                return true;
+            }
+            // what-if cells are not checked against the solution, so they
+            // have no place in a challenge
+            BoardMsg::StartSandbox if self.challenge.is_some() => false,
+            BoardMsg::StartSandbox => {
+                self.change_progress(|board| board.start_sandbox());
+                return true;
+            }
+            BoardMsg::CommitSandbox => {
+                self.change_progress(|board| board.commit_sandbox());
+                return true;
+            }
+            BoardMsg::DiscardSandbox => {
+                self.change_progress(|board| board.discard_sandbox());
+                return true;
+            }
+            BoardMsg::RetryChallenge => {
+                if let Some(challenge) = &mut self.challenge {
+                    challenge.mistakes = 0;
+                    challenge.store(&self.puzzle_code);
+                }
+                let width = self.board.width();
+                self.board.restore_progress(&Progress {
+                    field: Grid::new(width, width),
+                    sandbox: None,
+                });
+                self.history.clear();
+                self.store_progress();
+                self.stats = SolveStats::default();
+                self.stats.store(&self.puzzle_key("stats"));
+                return true;
             }
+            BoardMsg::TimerTick => return self.stats.tick(),
+            BoardMsg::VisibilityChange => return true,
         };
         if rerender {
-            self.puzzle_code = self.board.solution_ref().serialize_base64();
+            let new_code = self.board.puzzle_code();
+            let old_code = std::mem::replace(&mut self.puzzle_code, new_code);
+            if self.mode == BoardMode::Set && old_code != self.puzzle_code {
+                let (draft, new_code) = (&mut self.draft, &self.puzzle_code);
+                library::update(|library| library.update_draft(draft, new_code));
+            }
             ctx.props().puzzle.set(self.puzzle_code.clone().into());
             let navigator = ctx.link().navigator().unwrap();
-            let route = match self.mode {
-                BoardMode::Solve => Route::Solve {
+            let route = match (self.mode, &self.challenge) {
+                (BoardMode::Solve, Some(challenge)) => Route::SolveChallenge {
                     puzzle: self.puzzle_code.clone(),
+                    lives: challenge.lives,
                 },
-                BoardMode::Set => Route::Set {
+                (BoardMode::Solve, None) => Route::Solve {
                     puzzle: self.puzzle_code.clone(),
                 },
+                (BoardMode::Set, _) => match self.draft {
+                    Some(draft) => Route::SetDraft {
+                        puzzle: self.puzzle_code.clone(),
+                        draft,
+                    },
+                    None => Route::Set {
+                        puzzle: self.puzzle_code.clone(),
+                    },
+                },
+                (BoardMode::Clues, _) => Route::Clues,
             };
             navigator.replace(&route)
         }
@@ -101,16 +380,43 @@ impl Component for Board {
         let n_rows = n_hints + n_field_rows;
         let target_width_px = 500;
         let cell_width_px = target_width_px / n_rows;
-        let preview_width_px = cell_width_px * n_hints * 8 / 10;
-        let preview_margin_px = cell_width_px * n_hints / 10;
 
-        let grid_svg = grid_svg(n_hints, n_rows, cell_width_px);
-        let hints_svg = hints_svg(&self.board, cell_width_px);
-        let cells_svg = cells_svg(&self.board, self.mode, cell_width_px);
+        let content = match self.mode {
+            BoardMode::Solve => SheetContent::Progress,
+            BoardMode::Set | BoardMode::Clues => SheetContent::Solution,
+        };
+        let alternative = match self.mode {
+            BoardMode::Set => ctx.props().alternative.as_ref(),
+            _ => None,
+        };
+        let board_svg = sheet_svg(&self.board, content, alternative, target_width_px);
+        let underlay_svg = match (&ctx.props().underlay, self.mode) {
+            (Some(underlay), BoardMode::Set) => {
+                underlay.svg(cell_width_px * n_hints, cell_width_px * n_field_rows)
+            }
+            _ => html!(),
+        };
         let drag_sel_svg = self.drag.as_ref().map_or(html!(), |drag| {
-            selection_svg(&self.board, drag, cell_width_px)
+            selection_svg(
+                &self.board,
+                self.drag_cells(drag),
+                drag.button,
+                cell_width_px,
+            )
         });
 
+        let (heatmap_svg, heatmap_legend) = if self.mode == BoardMode::Set && ctx.props().heatmap {
+            heatmap_svg(&self.board, cell_width_px)
+        } else {
+            (html!(), html!())
+        };
+        let block_overlay_svg = self.block_overlay_svg(n_hints, cell_width_px);
+        let symmetry_svg = if self.mode == BoardMode::Set {
+            symmetry_guides_svg(&self.board, self.symmetry, cell_width_px)
+        } else {
+            html!()
+        };
+
         let offset_to_coord = move |(offset_x, offset_y): (i32, i32)| {
             let row = (offset_y / cell_width_px as i32) - n_hints as i32;
             let col = (offset_x / cell_width_px as i32) - n_hints as i32;
@@ -126,11 +432,22 @@ impl Component for Board {
         };
 
         let current_drag_end = self.drag.as_ref().map(|sel| sel.end);
+        let (pasting, cursor) = (self.pasting, self.cursor);
+        // shift-click asks for an explanation instead of changing the cell
+        let explaining = {
+            let mode = self.mode;
+            move |evt: &MouseEvent| mode == BoardMode::Solve && evt.shift_key()
+        };
         let onmousemove = link.batch_callback(move |evt: MouseEvent| {
             evt.prevent_default();
-            if evt.buttons() == 0 {
+            if explaining(&evt) {
                 return None;
             }
+            if evt.buttons() == 0 {
+                return offset_to_coord((evt.offset_x(), evt.offset_y()))
+                    .filter(|&coord| pasting && Some(coord) != cursor)
+                    .map(|(row, col)| Self::Message::Hover(row, col));
+            }
             offset_to_coord((evt.offset_x(), evt.offset_y())).and_then(|(row, col)| {
                 if Some((row, col)) == current_drag_end {
                     return None;
@@ -154,6 +471,9 @@ impl Component for Board {
         let oncontextmenu = onmousemove.clone();
         let onmouseup = link.batch_callback(move |evt: MouseEvent| {
             offset_to_coord((evt.offset_x(), evt.offset_y())).and_then(|(row, col)| {
+                if explaining(&evt) {
+                    return (evt.button() == 0).then_some(Self::Message::Explain(row, col));
+                }
                 match evt.button() {
                     0 => Some(Self::Message::CompleteDragSelection(
                         row,
@@ -170,36 +490,249 @@ impl Component for Board {
             })
         });
 
-        let preview_field = match self.mode {
-            BoardMode::Solve => self.board.field_ref(),
-            BoardMode::Set => self.board.solution_ref(),
+        let export_contents = match self.mode {
+            BoardMode::Solve => vec![
+                ("Puzzle", SheetContent::Clues),
+                ("Progress", SheetContent::Progress),
+                ("Solution", SheetContent::Solution),
+            ],
+            BoardMode::Set | BoardMode::Clues => vec![
+                ("Puzzle", SheetContent::Clues),
+                ("Solution", SheetContent::Solution),
+            ],
+        };
+        let export_buttons = export_contents
+            .into_iter()
+            .map(|(label, content)| {
+                let export = |format| link.callback(move |_| BoardMsg::Export(content, format));
+                html! {
+                    <span style="margin-left: 10px">
+                        {label}{": "}
+                        <button onclick={export(ExportFormat::Svg)}>{"SVG"}</button>
+                        <button onclick={export(ExportFormat::Png)}
+                                style="margin-left: 4px">{"PNG"}</button>
+                    </span>
+                }
+            })
+            .collect::<Html>();
+        let selection_buttons = if self.mode == BoardMode::Set && self.tool == DrawingTool::Select {
+            let has_selection = self.selection.is_some();
+            html! {
+                <p style="text-align: center">
+                    <button onclick={link.callback(|_| BoardMsg::Copy)}
+                            disabled={!has_selection}>{"Copy"}</button>
+                    <button onclick={link.callback(|_| BoardMsg::Cut)}
+                            disabled={!has_selection} style="margin-left: 4px">{"Cut"}</button>
+                    <button onclick={link.callback(|_| BoardMsg::Paste)}
+                            disabled={self.clipboard.is_none()}
+                            style="margin-left: 4px">{"Paste"}</button>
+                    <button onclick={link.callback(|_| BoardMsg::Deselect)}
+                            style="margin-left: 4px">{"Deselect"}</button>
+                    <br/>
+                    {
+                        if self.pasting {
+                            "Click where the top left corner of the block goes"
+                        } else {
+                            "Drag to select a block, drag the selection to move it"
+                        }
+                    }
+                </p>
+            }
+        } else {
+            html!()
+        };
+        let export_svg = self.export.map_or(html!(), |(content, _)| {
+            html! {
+                <div style="display: none">
+                    <svg ref={self.export_ref.clone()}
+                         width={target_width_px.to_string()}
+                         height={target_width_px.to_string()}>
+                        <style>{export::SHEET_STYLE}</style>
+                        <rect class="sheet-background" x="0" y="0"
+                              width={target_width_px.to_string()}
+                              height={target_width_px.to_string()}/>
+                        {sheet_svg(&self.board, content, None, target_width_px)}
+                    </svg>
+                </div>
+            }
+        });
+
+        let contradictions_svg = match self.mode {
+            BoardMode::Solve => contradictions_svg(&self.board, cell_width_px),
+            _ => html!(),
+        };
+        let satisfied_svg = match self.mode {
+            BoardMode::Solve if self.auto_mark => satisfied_hints_svg(&self.board, cell_width_px),
+            _ => html!(),
+        };
+        let solve_controls = match self.mode {
+            BoardMode::Solve => {
+                let onchange = link.callback(|e: Event| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    BoardMsg::AutoMark(input.checked())
+                });
+                let sandbox_buttons = if self.challenge.is_some() {
+                    html!()
+                } else if self.board.sandbox_ref().is_some() {
+                    html! {
+                        <>
+                            <button onclick={link.callback(|_| BoardMsg::CommitSandbox)}
+                                    style="margin-left: 4px">{"Keep what-if cells"}</button>
+                            <button onclick={link.callback(|_| BoardMsg::DiscardSandbox)}
+                                    style="margin-left: 4px">{"Discard what-if cells"}</button>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <button onclick={link.callback(|_| BoardMsg::StartSandbox)}
+                                title="Try out cells without committing to them"
+                                style="margin-left: 4px">{"What if…"}</button>
+                    }
+                };
+                let challenge_status = self.challenge.as_ref().map(Challenge::status);
+                let over = self.challenge.as_ref().is_some_and(Challenge::is_over);
+                html! {
+                    <p style="text-align: center">
+                        {self.stats.status()}{challenge_status}
+                        <br/>
+                        <button onclick={link.callback(|_| BoardMsg::Undo)}
+                                disabled={self.history.is_empty() || over}>{"Undo"}</button>
+                        {sandbox_buttons}
+                        <br/>
+                        <label for="auto_mark_inp">{"Auto-mark completed lines:"}</label>
+                        <input id="auto_mark_inp" type="checkbox"
+                               checked={self.auto_mark} {onchange}/>
+                    </p>
+                }
+            }
+            _ => html!(),
+        };
+        // neither the solver nor explanations help out in a challenge
+        let (playback_svg, playback_controls) = match self.mode {
+            BoardMode::Solve if self.challenge.is_some() => (html!(), html!()),
+            BoardMode::Solve => (
+                self.playback.line_svg(n_hints, n_rows, cell_width_px),
+                self.playback.controls(link),
+            ),
+            _ => (html!(), html!()),
+        };
+        let (explained_svg, explanation) = match (&self.explanation, self.mode) {
+            (_, BoardMode::Solve) if self.challenge.is_some() => (html!(), html!()),
+            (Some((cell, explanation)), BoardMode::Solve) => (
+                explained_cell_svg(*cell, n_hints, cell_width_px),
+                explanation_panel(*cell, explanation),
+            ),
+            (None, BoardMode::Solve) => (
+                html!(),
+                html! {
+                    <p style="text-align: center">
+                        {"Shift-click a cell to see why it is filled or empty"}
+                    </p>
+                },
+            ),
+            _ => (html!(), html!()),
+        };
+        let (game_over_svg, game_over_panel) = match &self.challenge {
+            Some(challenge) if self.mode == BoardMode::Solve => challenge.game_over(
+                n_rows * cell_width_px,
+                &self.puzzle_code,
+                link.callback(|_| BoardMsg::RetryChallenge),
+            ),
+            _ => (html!(), html!()),
+        };
+        let details = match self.mode {
+            BoardMode::Solve => details_header(self.board.metadata_ref(), self.board.is_solved()),
+            _ => html!(),
+        };
+        let completion = if self.mode == BoardMode::Solve && self.board.is_solved() {
+            self.stats.completion(best_record(&self.puzzle_code))
+        } else {
+            html!()
         };
 
         html! {
             <>
-                <svg id={"game-board"}
-                     width={target_width_px.to_string()}
-                     height={target_width_px.to_string()}
-                     {onmousemove} {onmouseup} {onclick} {oncontextmenu}>
-                    <NonogramPreview field={preview_field.clone()}
-                                     width_px={preview_width_px as u32}
-                                     margin_px={preview_margin_px as u32}/>
-                    {grid_svg}{hints_svg}{cells_svg}{drag_sel_svg}
-                </svg>
+                {details}
+                <div class="board-row">
+                    <svg id={"game-board"}
+                         width={target_width_px.to_string()}
+                         height={target_width_px.to_string()}
+                         {onmousemove} {onmouseup} {onclick} {oncontextmenu}>
+                        {underlay_svg}{contradictions_svg}{playback_svg}{board_svg}
+                        {satisfied_svg}{heatmap_svg}{drag_sel_svg}
+                        {block_overlay_svg}{symmetry_svg}{explained_svg}
+                        {game_over_svg}
+                    </svg>
+                    {explanation}{game_over_panel}{completion}
+                </div>
+                {solve_controls}
+                {playback_controls}
+                {heatmap_legend}
+                {selection_buttons}
+                <p style="text-align: center">{"Download"}{export_buttons}</p>
+                {export_svg}
             </>
         }
     }
 
-    fn changed(&mut self, ctx: &Context<Self>, _orig_props: &Self::Properties) -> bool {
-        let mut rerender = false;
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        self.sync_timer(ctx);
+        if let Some((content, format)) = self.export.take() {
+            match self.export_ref.cast::<Element>() {
+                Some(svg) => export::download(&svg, content, format),
+                None => log::warn!("Board SVG for export was not rendered"),
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, orig_props: &Self::Properties) -> bool {
+        let mut rerender = ctx.props().underlay != orig_props.underlay
+            || ctx.props().heatmap != orig_props.heatmap
+            || ctx.props().alternative != orig_props.alternative;
+        if ctx.props().mode != self.mode {
+            self.mode = ctx.props().mode;
+            rerender = true;
+        }
+        if ctx.props().draft != orig_props.draft {
+            self.draft = ctx.props().draft;
+        }
         let puzzle_from_prop = ctx.props().puzzle.as_ref();
-        if *puzzle_from_prop != self.puzzle_code {
+        if *puzzle_from_prop != self.puzzle_code || ctx.props().lives != orig_props.lives {
+            if self.stats.set_running(false) {
+                self.stats.store(&self.puzzle_key("stats"));
+            }
             self.board = BoardModel::from_serialized_solution(&puzzle_from_prop);
+            self.selection = None;
+            self.explanation = None;
+            self.history.clear();
+            // in Set mode the setting panel changed the puzzle
+            if self.mode == BoardMode::Set && *puzzle_from_prop != self.puzzle_code {
+                let (draft, new_code): (_, &str) = (&mut self.draft, puzzle_from_prop);
+                library::update(|library| library.update_draft(draft, new_code));
+            }
+            self.puzzle_code = puzzle_from_prop.to_string();
+            self.challenge = ctx
+                .props()
+                .lives
+                .map(|lives| Challenge::load(lives, &self.puzzle_code));
+            self.load_progress();
+            self.load_stats();
+            self.list_played();
+            self.playback.pause();
+            self.playback.line = None;
+            self.playback.status = None;
             log::info!("Updating puzzle from code");
             rerender = true;
         }
-        if ctx.props().mode != self.mode {
-            self.mode = ctx.props().mode;
+        if ctx.props().tool != self.tool {
+            self.tool = ctx.props().tool;
+            self.drag = None;
+            self.selection = None;
+            self.pasting = false;
+            rerender = true;
+        }
+        if ctx.props().symmetry != self.symmetry {
+            self.symmetry = ctx.props().symmetry;
             rerender = true;
         }
         rerender
@@ -209,15 +742,59 @@ impl Component for Board {
 impl Board {
     // returns true if the selection changed
     fn update_drag_selection(&mut self, row: i32, col: i32, btn: LeftRight) -> bool {
-        let start = self.drag.clone().map(|sel| sel.start).unwrap_or((row, col));
-        self.drag = Some(Drag {
-            start,
+        let drag = self.drag.get_or_insert(Drag {
+            start: (row, col),
             end: (row, col),
             button: btn,
+            path: vec![],
         });
+        drag.end = (row, col);
+        drag.button = btn;
+        drag.path.push((row, col));
         true
     }
 
+    /// Cells affected by a drag, depending on the drawing tool and symmetry in
+    /// Set mode
+    fn drag_cells(&self, drag: &Drag) -> Vec<(i32, i32)> {
+        if self.mode != BoardMode::Set {
+            return DragSelection::new(drag.start, drag.end).collect();
+        }
+        let width = self.board.width() as i32;
+        self.drawn_cells(drag)
+            .into_iter()
+            .filter(|&(row, col)| row >= 0 && col >= 0 && row < width && col < width)
+            .flat_map(|(row, col)| {
+                self.symmetry
+                    .images((row as usize, col as usize), width as usize, width as usize)
+            })
+            .map(|(row, col)| (row as i32, col as i32))
+            .unique()
+            .collect()
+    }
+
+    fn drawn_cells(&self, drag: &Drag) -> Vec<(i32, i32)> {
+        match self.tool {
+            DrawingTool::Rectangle | DrawingTool::Given => {
+                DragSelection::new(drag.start, drag.end).collect()
+            }
+            DrawingTool::Pencil => {
+                let mut points = drag.path.clone();
+                if points.is_empty() {
+                    points.push(drag.start);
+                }
+                Path::new(points).collect()
+            }
+            DrawingTool::Line => StraightLine::new(drag.start, drag.end).collect(),
+            DrawingTool::RectOutline => RectOutline::new(drag.start, drag.end).collect(),
+            DrawingTool::Ellipse => Ellipse::new(drag.start, drag.end).collect(),
+            DrawingTool::FloodFill => {
+                FloodFill::new(self.board.solution_ref(), drag.start).collect()
+            }
+            DrawingTool::Select => vec![],
+        }
+    }
+
     // returns true if any action was performed
     fn complete_drag_selection(
         &mut self,
@@ -230,37 +807,371 @@ impl Board {
             start: (row, col),
             end: (row, col),
             button: btn,
+            path: vec![(row, col)],
         });
         match mode {
+            BoardMode::Solve if self.challenge.as_ref().is_some_and(Challenge::is_over) => false,
             BoardMode::Solve => {
-                let start_cell_state = self
-                    .board
-                    .field(drag.start.0 as usize, drag.start.1 as usize);
-                let mut action = |row, col| match (drag.button, start_cell_state) {
-                    (LeftRight::Left, _) => self.board.fill(row, col),
-                    (LeftRight::Right, FieldCell::Marked) => self.board.unmark(row, col),
-                    (LeftRight::Right, _) => self.board.mark(row, col),
-                };
-                DragSelection::new(drag.start, drag.end)
-                    .map(|(row, col)| (row as usize, col as usize))
-                    .filter(|&(row, col)| action(row, col))
-                    .count()
-                    > 0
+                let auto_mark = self.auto_mark;
+                let challenge = self.challenge.is_some();
+                let (mut lives_lost, mut mistakes) = (0, 0);
+                let changed = self.change_progress(|board| {
+                    let start_cell_state =
+                        board.field_with_sandbox()[drag.start.0 as usize][drag.start.1 as usize];
+                    // what-if cells are guesses rather than mistakes
+                    let trying = board.sandbox_ref().is_some();
+                    let mut action = |row, col| {
+                        // the rest of a drag through a wrong fill is not applied
+                        if lives_lost > 0 {
+                            return false;
+                        }
+                        let belongs_filled = board.solution(row, col) == FieldCell::Filled;
+                        let (changed, wrong) = match (drag.button, start_cell_state) {
+                            // a wrong fill is turned into a mark and costs a life
+                            (LeftRight::Left, _)
+                                if challenge
+                                    && !trying
+                                    && board.field(row, col) == FieldCell::Empty
+                                    && !belongs_filled =>
+                            {
+                                lives_lost += 1;
+                                (board.mark(row, col), true)
+                            }
+                            (LeftRight::Left, _) => (board.fill(row, col), !belongs_filled),
+                            (LeftRight::Right, FieldCell::Marked) => {
+                                (board.unmark(row, col), false)
+                            }
+                            (LeftRight::Right, _) => (board.mark(row, col), belongs_filled),
+                        };
+                        if changed && wrong && !trying {
+                            mistakes += 1;
+                        }
+                        changed
+                    };
+                    let changed = DragSelection::new(drag.start, drag.end)
+                        .map(|(row, col)| (row as usize, col as usize))
+                        .filter(|&(row, col)| action(row, col))
+                        .count()
+                        > 0;
+                    // auto-marks are undone along with the action causing them
+                    if changed && auto_mark {
+                        board.mark_satisfied_lines();
+                    }
+                    changed
+                });
+                let challenge = self.challenge.as_mut().filter(|_| lives_lost > 0);
+                if let Some(challenge) = challenge {
+                    challenge.mistakes += lives_lost;
+                    challenge.store(&self.puzzle_code);
+                }
+                if changed {
+                    self.stats.record.moves += 1;
+                    self.stats.record.mistakes += mistakes;
+                    self.stats.store(&self.puzzle_key("stats"));
+                }
+                changed
             }
+            BoardMode::Set if self.tool == DrawingTool::Select => self.complete_block_drag(drag),
             BoardMode::Set => {
-                DragSelection::new(drag.start, drag.end)
+                let given_tool = self.tool == DrawingTool::Given;
+                self.drag_cells(&drag)
+                    .into_iter()
                     .map(|(row, col)| (row as usize, col as usize))
                     .filter(|&(row, col)| {
-                        self.board.set(row, col, drag.button == LeftRight::Left)
+                        let left = drag.button == LeftRight::Left;
+                        if given_tool {
+                            self.board.set_given(row, col, left)
+                        } else {
+                            self.board.set(row, col, left)
+                        }
                     })
                     .count()
                     > 0
             }
+            BoardMode::Clues => false,
+        }
+    }
+}
+
+impl Board {
+    fn update_playback(&mut self, ctx: &Context<Self>, msg: PlaybackMsg) {
+        match msg {
+            PlaybackMsg::Play => {
+                self.playback.pause();
+                self.playback.playing = self.playback_step();
+                if self.playback.playing {
+                    self.playback.schedule_tick(ctx.link());
+                }
+            }
+            PlaybackMsg::Pause => self.playback.pause(),
+            PlaybackMsg::Step => {
+                self.playback.pause();
+                self.playback_step();
+            }
+            PlaybackMsg::Speed(delay_ms) => self.playback.delay_ms = delay_ms,
+            PlaybackMsg::Tick(generation) => {
+                if !self.playback.playing || generation != self.playback.generation {
+                    return;
+                }
+                self.playback.playing = self.playback_step();
+                if self.playback.playing {
+                    self.playback.schedule_tick(ctx.link());
+                }
+            }
+        }
+    }
+
+    /// Apply the line solver's next deduction to the field, return false if
+    /// there was none
+    fn playback_step(&mut self) -> bool {
+        let clues = Clues::from_solution(self.board.solution_ref());
+        let field = self.board.field_with_sandbox();
+        let next = next_deduction(&clues, &field, self.playback.line);
+        self.playback.status = match next {
+            Ok(Some((line, cells))) => {
+                self.change_progress(|board| {
+                    for (row, col, cell) in cells {
+                        match cell {
+                            FieldCell::Filled => board.fill(row, col),
+                            _ => board.mark(row, col),
+                        };
+                    }
+                    true
+                });
+                self.playback.line = Some(line);
+                self.stats.assisted = true;
+                return true;
+            }
+            Ok(None) if self.board.is_solved() => Some("Solved".to_owned()),
+            Ok(None) => Some("Nothing more can be deduced line by line".to_owned()),
+            Err(Contradiction(Some(line))) => Some(format!(
+                "{line} cannot be completed, the board has mistakes"
+            )),
+            Err(Contradiction(None)) => Some("The board has mistakes".to_owned()),
+        };
+        self.playback.line = None;
+        false
+    }
+
+    /// Apply a change to the player's progress, keeping it undoable and
+    /// stored, return whether the change did anything
+    fn change_progress(&mut self, change: impl FnOnce(&mut BoardModel) -> bool) -> bool {
+        let before = self.board.progress();
+        if !change(&mut self.board) {
+            return false;
+        }
+        self.history.push(before);
+        self.store_progress();
+        true
+    }
+
+    fn puzzle_key(&self, name: &str) -> String {
+        stored_key(name, &self.puzzle_code, self.challenge.is_some())
+    }
+
+    fn store_progress(&self) {
+        storage::store(
+            &self.puzzle_key("progress"),
+            &self.board.progress().serialize(),
+        );
+    }
+
+    /// Continue where the player left this puzzle in Solve mode
+    fn load_progress(&mut self) {
+        if self.mode != BoardMode::Solve {
+            return;
+        }
+        let stored = storage::load(&self.puzzle_key("progress"));
+        if let Some(progress) = stored.as_deref().and_then(Progress::deserialize) {
+            self.board.restore_progress(&progress);
+        }
+    }
+
+    fn list_played(&self) {
+        if self.mode == BoardMode::Solve {
+            library::update(|library| library.add_played(&self.puzzle_code));
+        }
+    }
+
+    /// Continue the timer and counters of the attempt at the puzzle
+    fn load_stats(&mut self) {
+        self.stats = match self.mode {
+            BoardMode::Solve => SolveStats::load(&self.puzzle_key("stats")),
+            _ => SolveStats::default(),
+        };
+        // an attempt finished earlier is not recorded again
+        self.solved = self.mode == BoardMode::Solve && self.board.is_solved();
+    }
+
+    /// Run the timer only while the player is solving and can see the board,
+    /// record the attempt once it is solved
+    fn sync_timer(&mut self, ctx: &Context<Self>) {
+        let solved = self.mode == BoardMode::Solve && self.board.is_solved();
+        let over = self.challenge.as_ref().is_some_and(Challenge::is_over);
+        let solving = self.mode == BoardMode::Solve && !solved && !over;
+        if self.stats.set_running(solving && !tab_hidden()) {
+            self.stats.store(&self.puzzle_key("stats"));
+        }
+        if solved && !self.solved {
+            self.stats.store_best(&self.puzzle_code);
+        }
+        self.solved = solved;
+        self.stats.schedule_tick(ctx.link());
+    }
+
+    fn copy_selection(&mut self) {
+        if let Some(selection) = self.selection {
+            let block = self
+                .board
+                .solution_ref()
+                .sub_grid(selection.rows(), selection.cols());
+            let format = TextFormat {
+                trim: false,
+                ..TextFormat::default()
+            };
+            copy_text(&block.to_text(&format));
+            self.clipboard = Some(block);
+        }
+    }
+
+    /// Select, move or paste a block with the select tool,
+    /// returns true if the solution changed
+    fn complete_block_drag(&mut self, drag: Drag) -> bool {
+        let width = self.board.width();
+        if drag.button == LeftRight::Right {
+            self.selection = None;
+            self.pasting = false;
+            return false;
+        }
+        if self.pasting {
+            self.pasting = false;
+            let Some(block) = &self.clipboard else {
+                return false;
+            };
+            self.selection = Selection::of_picture(drag.end, block).clipped(width);
+            return self
+                .board
+                .set_region(drag.end.0 as isize, drag.end.1 as isize, block);
+        }
+        match self.selection {
+            Some(selection) if selection.contains(drag.start) && drag.start != drag.end => {
+                let offset = (drag.end.0 - drag.start.0, drag.end.1 - drag.start.1);
+                let block = self
+                    .board
+                    .solution_ref()
+                    .sub_grid(selection.rows(), selection.cols());
+                let moved = selection.moved(offset);
+                self.board.clear_region(selection.rows(), selection.cols());
+                let (row, col) = moved.top_left();
+                self.board.set_region(row as isize, col as isize, &block);
+                self.selection = moved.clipped(width);
+                true
+            }
+            _ => {
+                self.selection = Some(Selection::new(drag.start, drag.end));
+                false
+            }
+        }
+    }
+
+    /// Outline of the selection or the block being moved or pasted
+    fn block_overlay_svg(&self, n_hints: usize, cell_width_px: usize) -> Html {
+        if self.mode != BoardMode::Set || self.tool != DrawingTool::Select {
+            return html!();
+        }
+        let position = self.drag.as_ref().map(|drag| drag.end).or(self.cursor);
+        if self.pasting {
+            return match (&self.clipboard, position) {
+                (Some(block), Some(position)) => block_svg(
+                    &Selection::of_picture(position, block),
+                    Some(block),
+                    n_hints,
+                    cell_width_px,
+                ),
+                _ => html!(),
+            };
+        }
+        match (self.selection, &self.drag) {
+            (Some(selection), Some(drag))
+                if drag.button == LeftRight::Left && selection.contains(drag.start) =>
+            {
+                let offset = (drag.end.0 - drag.start.0, drag.end.1 - drag.start.1);
+                let block = self
+                    .board
+                    .solution_ref()
+                    .sub_grid(selection.rows(), selection.cols());
+                block_svg(
+                    &selection.moved(offset),
+                    Some(&block),
+                    n_hints,
+                    cell_width_px,
+                )
+            }
+            (_, Some(drag)) if drag.button == LeftRight::Left => block_svg(
+                &Selection::new(drag.start, drag.end),
+                None,
+                n_hints,
+                cell_width_px,
+            ),
+            (Some(selection), _) => block_svg(&selection, None, n_hints, cell_width_px),
+            _ => html!(),
         }
     }
 }
 
-fn cells_svg(board: &BoardModel, mode: BoardMode, cell_width_px: usize) -> Html {
+/// Storage key of something about a puzzle, kept apart for challenges
+fn stored_key(name: &str, puzzle_code: &str, challenge: bool) -> String {
+    if challenge {
+        format!("challenge-{name}-{puzzle_code}")
+    } else {
+        format!("{name}-{puzzle_code}")
+    }
+}
+
+/// Everything drawn on the board except for the drag selection, with the cells
+/// in which `alternative` differs from the solution highlighted
+pub fn sheet_svg(
+    board: &BoardModel,
+    content: SheetContent,
+    alternative: Option<&Grid<FieldCell>>,
+    target_width_px: usize,
+) -> Html {
+    let n_hints = board.hint_len();
+    let n_rows = n_hints + board.width();
+    let cell_width_px = target_width_px / n_rows;
+    let preview_width_px = cell_width_px * n_hints * 8 / 10;
+    let preview_margin_px = cell_width_px * n_hints / 10;
+    // an alternative found before the board was resized no longer applies
+    let solution = board.solution_ref();
+    let alternative = alternative.filter(|alternative| {
+        alternative.width() == solution.width() && alternative.height() == solution.height()
+    });
+
+    let preview_field = match content {
+        SheetContent::Clues => None,
+        SheetContent::Progress => Some(board.field_ref()),
+        SheetContent::Solution => Some(alternative.unwrap_or(board.solution_ref())),
+    };
+    html! {
+        <>
+            if let Some(field) = preview_field {
+                <NonogramPreview field={field.clone()}
+                                 width_px={preview_width_px as u32}
+                                 margin_px={preview_margin_px as u32}/>
+            }
+            {grid_svg(n_hints, n_rows, cell_width_px)}
+            {hints_svg(board, cell_width_px)}
+            {cells_svg(board, content, alternative, cell_width_px)}
+        </>
+    }
+}
+
+fn cells_svg(
+    board: &BoardModel,
+    content: SheetContent,
+    alternative: Option<&Grid<FieldCell>>,
+    cell_width_px: usize,
+) -> Html {
     let n_hints = board.hint_len();
     let n_field_rows = board.width();
     let filled_cell_svg = |xi: usize, yi: usize, correct: bool| {
@@ -286,10 +1197,78 @@ fn cells_svg(board: &BoardModel, mode: BoardMode, cell_width_px: usize) -> Html
             <text {x} {y} fill="black">{"X"}</text>
         }
     };
-    match mode {
-        BoardMode::Solve => iproduct!(0..n_field_rows, 0..n_field_rows)
+    // givens are drawn in their own style, they cannot be changed
+    let given_cell_svg = |xi: usize, yi: usize| match board.givens_ref()[yi][xi] {
+        FieldCell::Filled => {
+            let x = (cell_width_px * (xi + n_hints) + 1).to_string();
+            let y = (cell_width_px * (yi + n_hints) + 1).to_string();
+            let width = (cell_width_px - 2).to_string();
+            let height = width.clone();
+            html! { <rect {x} {y} {width} {height} class="game-cell-given"/> }
+        }
+        FieldCell::Marked => {
+            let x = cell_width_px * (xi + n_hints) + cell_width_px / 2 - 4;
+            let y = cell_width_px * (yi + n_hints) + cell_width_px / 2 + 6;
+            let (x, y) = (x.to_string(), y.to_string());
+            html! { <text {x} {y} class="game-cell-given-mark">{"X"}</text> }
+        }
+        FieldCell::Empty => html! {},
+    };
+    // what-if cells do not tell whether they are correct
+    let maybe_cell_svg = |xi: usize, yi: usize| match board
+        .sandbox_ref()
+        .map_or(FieldCell::Empty, |sandbox| sandbox[yi][xi])
+    {
+        FieldCell::Filled => {
+            let x = (cell_width_px * (xi + n_hints) + 1).to_string();
+            let y = (cell_width_px * (yi + n_hints) + 1).to_string();
+            let width = (cell_width_px - 2).to_string();
+            let height = width.clone();
+            html! { <rect {x} {y} {width} {height} class="game-cell-maybe"/> }
+        }
+        FieldCell::Marked => {
+            let x = cell_width_px * (xi + n_hints) + cell_width_px / 2 - 4;
+            let y = cell_width_px * (yi + n_hints) + cell_width_px / 2 + 6;
+            let (x, y) = (x.to_string(), y.to_string());
+            html! { <text {x} {y} class="game-cell-maybe-mark">{"X"}</text> }
+        }
+        FieldCell::Empty => html! {},
+    };
+    let given_marker_svg = |xi: usize, yi: usize| {
+        let cx = (cell_width_px * (xi + n_hints) + cell_width_px / 5).to_string();
+        let cy = (cell_width_px * (yi + n_hints) + cell_width_px / 5).to_string();
+        let r = (cell_width_px / 8).max(2).to_string();
+        html! { <circle {cx} {cy} {r} class="game-cell-given-marker"/> }
+    };
+    let difference_svg = |xi: usize, yi: usize| {
+        let Some(alternative) = alternative else {
+            return html!();
+        };
+        let alternative_filled = alternative[yi][xi] == FieldCell::Filled;
+        if alternative_filled == (board.solution(yi, xi) == FieldCell::Filled) {
+            return html!();
+        }
+        let x = (cell_width_px * (xi + n_hints) + 1).to_string();
+        let y = (cell_width_px * (yi + n_hints) + 1).to_string();
+        let width = (cell_width_px - 2).to_string();
+        let height = width.clone();
+        let class = if alternative_filled {
+            "game-cell-alternative-filled"
+        } else {
+            "game-cell-alternative-empty"
+        };
+        html! { <rect {x} {y} {width} {height} {class}/> }
+    };
+    let field_cells = iproduct!(0..n_field_rows, 0..n_field_rows);
+    match content {
+        SheetContent::Clues => field_cells
+            .filter(|&(xi, yi)| board.is_given(yi, xi))
+            .map(|(xi, yi)| given_cell_svg(xi, yi))
+            .collect(),
+        SheetContent::Progress => field_cells
             .map(|(xi, yi)| match board.field(yi, xi) {
-                FieldCell::Empty => html! {},
+                _ if board.is_given(yi, xi) => given_cell_svg(xi, yi),
+                FieldCell::Empty => maybe_cell_svg(xi, yi),
                 FieldCell::Filled => {
                     let correct = board.solution(yi, xi) == FieldCell::Filled;
                     filled_cell_svg(xi, yi, correct)
@@ -297,17 +1276,30 @@ fn cells_svg(board: &BoardModel, mode: BoardMode, cell_width_px: usize) -> Html
                 FieldCell::Marked => marked_cell_svg(xi, yi),
             })
             .collect(),
-        BoardMode::Set => iproduct!(0..n_field_rows, 0..n_field_rows)
-            .map(|(xi, yi)| match board.solution(yi, xi) {
-                FieldCell::Empty => html! {},
-                FieldCell::Filled => filled_cell_svg(xi, yi, true),
-                FieldCell::Marked => marked_cell_svg(xi, yi),
+        SheetContent::Solution => field_cells
+            .map(|(xi, yi)| {
+                let cell = match board.solution(yi, xi) {
+                    FieldCell::Empty => html! {},
+                    FieldCell::Filled => filled_cell_svg(xi, yi, true),
+                    FieldCell::Marked => marked_cell_svg(xi, yi),
+                };
+                let given_marker = if board.is_given(yi, xi) {
+                    given_marker_svg(xi, yi)
+                } else {
+                    html!()
+                };
+                html! { <>{cell}{difference_svg(xi, yi)}{given_marker}</> }
             })
             .collect(),
     }
 }
 
-fn selection_svg(board: &BoardModel, drag: &Drag, cell_width_px: usize) -> Html {
+fn selection_svg(
+    board: &BoardModel,
+    cells: Vec<(i32, i32)>,
+    button: LeftRight,
+    cell_width_px: usize,
+) -> Html {
     let n_hints = board.hint_len();
     let selected_for_fill_svg = |xi: usize, yi: usize| {
         let x = cell_width_px * (xi + n_hints) + 1;
@@ -328,15 +1320,132 @@ fn selection_svg(board: &BoardModel, drag: &Drag, cell_width_px: usize) -> Html
             <text {x} {y} fill="grey">{"X"}</text>
         }
     };
-    DragSelection::new(drag.start, drag.end)
+    cells
+        .into_iter()
         .map(|(row, col)| (col as usize, row as usize))
-        .map(|(xi, yi)| match drag.button {
+        .map(|(xi, yi)| match button {
             LeftRight::Left => selected_for_fill_svg(xi, yi),
             LeftRight::Right => selected_for_mark_svg(xi, yi),
         })
         .collect()
 }
 
+/// Tint behind the hints of every line that cannot match its clue anymore,
+/// judged from the player's fills and marks only
+fn contradictions_svg(board: &BoardModel, cell_width_px: usize) -> Html {
+    let clues = Clues::from_solution(board.solution_ref());
+    hint_band_svg(
+        contradicting_lines(&clues, &board.field_with_sandbox()),
+        "hint-contradiction",
+        board.hint_len(),
+        cell_width_px,
+    )
+}
+
+/// Veil dimming the hints of every line whose fills match its clue
+fn satisfied_hints_svg(board: &BoardModel, cell_width_px: usize) -> Html {
+    let clues = Clues::from_solution(board.solution_ref());
+    hint_band_svg(
+        clues.satisfied_lines(&board.field_with_sandbox()),
+        "hint-satisfied",
+        board.hint_len(),
+        cell_width_px,
+    )
+}
+
+/// A rectangle of the given class over the hints of each line
+fn hint_band_svg(
+    lines: Vec<Line>,
+    class: &'static str,
+    n_hints: usize,
+    cell_width_px: usize,
+) -> Html {
+    let px = |cells: usize| (cells * cell_width_px).to_string();
+    lines
+        .into_iter()
+        .map(|line| {
+            let (x, y, width, height) = match line {
+                Line::Row(row) => (0, n_hints + row, n_hints, 1),
+                Line::Col(col) => (n_hints + col, 0, 1, n_hints),
+            };
+            html! {
+                <rect x={px(x)} y={px(y)} width={px(width)} height={px(height)} {class}/>
+            }
+        })
+        .collect()
+}
+
+/// Field cells coloured by the pass in which the line solver determines them,
+/// or as undetermined, along with a summary
+fn heatmap_svg(board: &BoardModel, cell_width_px: usize) -> (Html, Html) {
+    let Ok(passes) = deduction_passes(&Clues::from_solution(board.solution_ref())) else {
+        return (html!(), html!());
+    };
+    let n_hints = board.hint_len();
+    let cells = iproduct!(0..board.width(), 0..board.width())
+        .map(|(row, col)| (row, col, passes[row][col]));
+    let max_pass = cells
+        .clone()
+        .filter_map(|(_, _, pass)| pass)
+        .max()
+        .unwrap_or(0);
+    let n_undetermined = cells.clone().filter(|(_, _, pass)| pass.is_none()).count();
+    let svg = cells
+        .map(|(row, col, pass)| {
+            let fill = match pass {
+                // later passes in lighter green
+                Some(pass) => {
+                    format!("hsl(120, 60%, {}%)", 30 + 45 * pass / max_pass.max(1))
+                }
+                None => "hsl(0, 70%, 50%)".to_owned(),
+            };
+            let x = (cell_width_px * (col + n_hints)).to_string();
+            let y = (cell_width_px * (row + n_hints)).to_string();
+            let width = cell_width_px.to_string();
+            let height = width.clone();
+            html! {
+                <rect {x} {y} {width} {height} {fill} class="heatmap-cell"/>
+            }
+        })
+        .collect::<Html>();
+    let summary = if n_undetermined == 0 {
+        format!("The line solver determines every cell in {max_pass} passes over all lines")
+    } else {
+        format!(
+            "{n_undetermined} cells (red) stay undetermined, solving them needs guessing \
+             or the puzzle has several solutions"
+        )
+    };
+    (svg, html! { <p style="text-align: center">{summary}</p> })
+}
+
+/// Axes or centre of the symmetry used for drawing
+fn symmetry_guides_svg(board: &BoardModel, symmetry: Symmetry, cell_width_px: usize) -> Html {
+    let start_px = cell_width_px * board.hint_len();
+    let end_px = start_px + cell_width_px * board.width();
+    let centre_px = (start_px + end_px) / 2;
+    let (start, end, centre) = (
+        start_px.to_string(),
+        end_px.to_string(),
+        centre_px.to_string(),
+    );
+    let class = "symmetry-guide";
+    html! {
+        <>
+            if symmetry.has_vertical_axis() {
+                <line x1={centre.clone()} y1={start.clone()}
+                      x2={centre.clone()} y2={end.clone()} {class}/>
+            }
+            if symmetry.has_horizontal_axis() {
+                <line x1={start} y1={centre.clone()} x2={end} y2={centre.clone()} {class}/>
+            }
+            if symmetry == Symmetry::Rotational {
+                <circle cx={centre.clone()} cy={centre} r="5" {class}/>
+            }
+        </>
+    }
+}
+
 fn hints_svg(board: &BoardModel, cell_width_px: usize) -> Html {
     let n_hints = board.hint_len();
     let n_field_rows = board.width();
@@ -378,17 +1487,23 @@ fn grid_svg(
 ) -> yew::virtual_dom::VNode {
     let width_px = cell_width_px * n_rows;
     (n_hints..n_rows)
-        .map(|xi| cell_width_px * xi)
+        .map(|xi| (cell_width_px * xi, (xi - n_hints) % 5 == 0))
         .into_iter()
-        .map(|x| {
+        .map(|(x, major)| {
+            // every fifth line is emphasized when printing
+            let class = if major {
+                "game-grid-line game-grid-line-major"
+            } else {
+                "game-grid-line"
+            };
             html! {
                 <>
                     <line x1={x.to_string()} y1="0"
                           x2={x.to_string()} y2={width_px.to_string()}
-                          class={"game-grid-line"} />
+                          {class} />
                     <line x1="0" y1={x.to_string()}
                           x2={width_px.to_string()} y2={x.to_string()}
-                          class={"game-grid-line"} />
+                          {class} />
                 </>
             }
         })
@@ -0,0 +1,84 @@
+use super::stored_key;
+use crate::routes::Route;
+use crate::storage;
+
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Solving where every wrong fill costs one of a limited number of lives
+pub struct Challenge {
+    pub lives: u32,
+    pub mistakes: u32,
+}
+
+pub fn mistakes_key(puzzle_code: &str) -> String {
+    stored_key("mistakes", puzzle_code, true)
+}
+
+impl Challenge {
+    /// Challenge on a puzzle, with the mistakes made in earlier visits
+    pub fn load(lives: u32, puzzle_code: &str) -> Self {
+        let mistakes = storage::load(&mistakes_key(puzzle_code))
+            .and_then(|mistakes| mistakes.parse().ok())
+            .unwrap_or(0);
+        Self { lives, mistakes }
+    }
+
+    pub fn store(&self, puzzle_code: &str) {
+        storage::store(&mistakes_key(puzzle_code), &self.mistakes.to_string());
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.mistakes >= self.lives
+    }
+
+    pub fn lives_left(&self) -> u32 {
+        self.lives.saturating_sub(self.mistakes)
+    }
+
+    /// Hearts for the lives left and lost
+    pub fn status(&self) -> Html {
+        let hearts = "♥".repeat(self.lives_left() as usize)
+            + &"♡".repeat(self.lives.min(self.mistakes) as usize);
+        html! {
+            <span style="margin-left: 10px">
+                <span class="challenge-lives">{hearts}</span>
+                {format!(" {} of {} lives left", self.lives_left(), self.lives)}
+            </span>
+        }
+    }
+
+    /// Veil over the board and a summary once all lives are lost
+    pub fn game_over(
+        &self,
+        board_width_px: usize,
+        puzzle_code: &str,
+        onretry: Callback<MouseEvent>,
+    ) -> (Html, Html) {
+        if !self.is_over() {
+            return (html!(), html!());
+        }
+        let size = board_width_px.to_string();
+        let center = (board_width_px / 2).to_string();
+        let veil = html! {
+            <>
+                <rect x="0" y="0" width={size.clone()} height={size} class="game-over-veil"/>
+                <text x={center.clone()} y={center} text-anchor="middle"
+                      class="game-over-text">{"Game over"}</text>
+            </>
+        };
+        let panel = html! {
+            <div class="panel" style="text-align: center">
+                <h3>{"Game over"}</h3>
+                <p>{format!("All {} lives are lost.", self.lives)}</p>
+                <button onclick={onretry}>{"Try again"}</button>
+                <span style="margin-left: 10px">
+                    <Link<Route> to={Route::Solve { puzzle: puzzle_code.to_owned() }}>
+                        {"Solve without lives"}
+                    </Link<Route>>
+                </span>
+            </div>
+        };
+        (veil, panel)
+    }
+}
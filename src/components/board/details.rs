@@ -0,0 +1,35 @@
+use crate::models::metadata::Metadata;
+
+use yew::prelude::*;
+
+/// Title, author, date and description of the puzzle shown above the board,
+/// the title possibly held back until the puzzle is solved
+pub fn details_header(metadata: &Metadata, solved: bool) -> Html {
+    if metadata.is_empty() {
+        return html!();
+    }
+    let title = match metadata.title.as_str() {
+        "" => html!(),
+        _ if metadata.hide_title && !solved => html! {
+            <h2 class="puzzle-title-hidden">{"Title revealed once solved"}</h2>
+        },
+        title => html! { <h2>{title}</h2> },
+    };
+    let byline = match (metadata.author.as_str(), metadata.created.as_str()) {
+        ("", "") => String::new(),
+        (author, "") => format!("by {author}"),
+        ("", created) => format!("set {created}"),
+        (author, created) => format!("by {author}, {created}"),
+    };
+    html! {
+        <div class="puzzle-details">
+            {title}
+            if !byline.is_empty() {
+                <p class="puzzle-byline">{byline}</p>
+            }
+            if !metadata.description.is_empty() {
+                <p>{&metadata.description}</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,45 @@
+use crate::models::explanation::Explanation;
+
+use yew::prelude::*;
+
+/// Outline of the cell being explained
+pub fn explained_cell_svg(
+    (row, col): (usize, usize),
+    n_hints: usize,
+    cell_width_px: usize,
+) -> Html {
+    let x = (cell_width_px * (col + n_hints)).to_string();
+    let y = (cell_width_px * (row + n_hints)).to_string();
+    let width = cell_width_px.to_string();
+    let height = width.clone();
+    html! { <rect {x} {y} {width} {height} class="explained-cell"/> }
+}
+
+/// Readable list of the deductions that determine a cell
+pub fn explanation_panel((row, col): (usize, usize), explanation: &Explanation) -> Html {
+    let body = match explanation {
+        Explanation::Known => {
+            html! { <p>{"This cell is decided on the board already."}</p> }
+        }
+        Explanation::Undetermined => html! {
+            <p>{"Line by line reasoning cannot decide this cell from the current board."}</p>
+        },
+        Explanation::Contradiction(Some(line)) => html! {
+            <p>{format!("{line} cannot be completed from the current board, check it for mistakes.")}</p>
+        },
+        Explanation::Contradiction(None) => html! {
+            <p>{"The current board contains mistakes."}</p>
+        },
+        Explanation::Steps(steps) => html! {
+            <ol>
+                { steps.iter().map(|step| html! { <li>{step.to_string()}</li> }).collect::<Html>() }
+            </ol>
+        },
+    };
+    html! {
+        <div class="panel explanation">
+            <h3>{format!("Row {}, column {}", row + 1, col + 1)}</h3>
+            {body}
+        </div>
+    }
+}
@@ -0,0 +1,129 @@
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CanvasRenderingContext2d, Element, HtmlAnchorElement, HtmlCanvasElement, HtmlImageElement,
+    XmlSerializer,
+};
+
+/// What a standalone rendering of the board shows besides grid and hints
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SheetContent {
+    Clues,
+    Progress,
+    Solution,
+}
+
+impl SheetContent {
+    pub fn file_stem(&self) -> &'static str {
+        match self {
+            SheetContent::Clues => "nonogram-puzzle",
+            SheetContent::Progress => "nonogram-progress",
+            SheetContent::Solution => "nonogram-solution",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Svg,
+    Png,
+}
+
+/// Board styles from index.html, embedded so exported files look the same
+pub const SHEET_STYLE: &str = "
+    svg { fill: #303030; font-family: sans-serif; }
+    .sheet-background { fill: #FFFFFF; }
+    .game-grid-line { stroke: #B2B2B2; stroke-width: 2; }
+    .game-cell-filled { fill: #6D6D6D; stroke-width: 1; stroke: #4E4E4E; }
+    .game-cell-filled-incorrect { fill: #8D6D6D; stroke-width: 1; stroke: #6E4E4E; }
+    .game-cell-preview { fill: #000000; }
+    .game-cell-given { fill: #2E4E6E; stroke-width: 1; stroke: #1E3E5E; }
+    .game-cell-given-mark { fill: #2E4E6E; }
+    .game-cell-given-marker { fill: #4080D0; }
+    .game-cell-maybe { fill: #7070C0; fill-opacity: .7; stroke-width: 1; stroke: #5050A0; }
+    .game-cell-maybe-mark { fill: #5050A0; }
+";
+
+// rasterize at a higher resolution than shown on screen
+const PNG_SCALE: u32 = 2;
+
+/// Save a rendered board SVG element as a file
+pub fn download(svg: &Element, content: SheetContent, format: ExportFormat) {
+    let markup = XmlSerializer::new()
+        .and_then(|serializer| serializer.serialize_to_string(svg))
+        .expect("Could not serialize board SVG");
+    let data_url = format!("data:image/svg+xml;base64,{}", base64::encode(markup));
+    match format {
+        ExportFormat::Svg => {
+            click_download_link(&data_url, &format!("{}.svg", content.file_stem()));
+        }
+        ExportFormat::Png => {
+            let width = svg.get_attribute("width").and_then(|w| w.parse().ok());
+            let height = svg.get_attribute("height").and_then(|h| h.parse().ok());
+            rasterize(
+                &data_url,
+                width.unwrap_or(500),
+                height.unwrap_or(500),
+                format!("{}.png", content.file_stem()),
+            );
+        }
+    }
+}
+
+/// Draw the SVG found at `url` onto a canvas once loaded and save as PNG
+fn rasterize(url: &str, width: u32, height: u32, filename: String) {
+    let image = HtmlImageElement::new().expect("Could not create image element");
+    let onload = {
+        let image = image.clone();
+        Closure::once_into_js(move || {
+            let canvas = web_sys::window()
+                .expect("Could not get window")
+                .document()
+                .expect("Could not get document")
+                .create_element("canvas")
+                .expect("Could not create canvas")
+                .unchecked_into::<HtmlCanvasElement>();
+            canvas.set_width(width * PNG_SCALE);
+            canvas.set_height(height * PNG_SCALE);
+            let context = canvas
+                .get_context("2d")
+                .ok()
+                .flatten()
+                .expect("Could not get 2d context")
+                .unchecked_into::<CanvasRenderingContext2d>();
+            let drawn = context.draw_image_with_html_image_element_and_dw_and_dh(
+                &image,
+                0.0,
+                0.0,
+                (width * PNG_SCALE) as f64,
+                (height * PNG_SCALE) as f64,
+            );
+            match (drawn, canvas.to_data_url_with_type("image/png")) {
+                (Ok(()), Ok(data_url)) => click_download_link(&data_url, &filename),
+                _ => log::warn!("Could not rasterize board"),
+            }
+        })
+    };
+    image.set_onload(Some(onload.unchecked_ref()));
+    image.set_src(url);
+}
+
+/// Save text as a file
+pub fn download_text(text: &str, filename: &str) {
+    let data_url = format!("data:text/plain;base64,{}", base64::encode(text));
+    click_download_link(&data_url, filename);
+}
+
+fn click_download_link(href: &str, filename: &str) {
+    let anchor = web_sys::window()
+        .expect("Could not get window")
+        .document()
+        .expect("Could not get document")
+        .create_element("a")
+        .expect("Could not create anchor")
+        .unchecked_into::<HtmlAnchorElement>();
+    anchor.set_href(href);
+    anchor.set_download(filename);
+    anchor.click();
+    log::info!("Downloading {filename}");
+}
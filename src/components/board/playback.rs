@@ -0,0 +1,121 @@
+use super::{Board, BoardMsg};
+use crate::models::clues::Line;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use yew::html::Scope;
+use yew::prelude::*;
+
+/// Delays between deductions offered, in milliseconds
+const SPEEDS: [(&str, u32); 3] = [("Slow", 1200), ("Normal", 500), ("Fast", 100)];
+
+pub enum PlaybackMsg {
+    Play,
+    Pause,
+    Step,
+    /// Delay between deductions in milliseconds
+    Speed(u32),
+    /// Time for the next deduction, if the generation is still current
+    Tick(u32),
+}
+
+/// Line solver replaying its deductions on the player's field
+pub struct Playback {
+    pub playing: bool,
+    /// Line the last deduction was made in
+    pub line: Option<Line>,
+    pub delay_ms: u32,
+    /// Counts play and pause presses so outdated ticks can be ignored
+    pub generation: u32,
+    /// Why the playback stopped
+    pub status: Option<String>,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            playing: false,
+            line: None,
+            delay_ms: SPEEDS[1].1,
+            generation: 0,
+            status: None,
+        }
+    }
+}
+
+impl Playback {
+    /// Stop playing, ticks scheduled so far have no effect anymore
+    pub fn pause(&mut self) {
+        self.playing = false;
+        self.generation += 1;
+    }
+
+    /// Ask for the next deduction after the current delay
+    pub fn schedule_tick(&self, link: &Scope<Board>) {
+        let link = link.clone();
+        let generation = self.generation;
+        let tick = Closure::once_into_js(move || {
+            link.send_message(BoardMsg::Playback(PlaybackMsg::Tick(generation)))
+        });
+        let scheduled = web_sys::window().and_then(|window| {
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    tick.unchecked_ref(),
+                    self.delay_ms as i32,
+                )
+                .ok()
+        });
+        if scheduled.is_none() {
+            log::warn!("Could not schedule the next solver step");
+        }
+    }
+
+    pub fn controls(&self, link: &Scope<Board>) -> Html {
+        let message = |msg: fn() -> PlaybackMsg| link.callback(move |_| BoardMsg::Playback(msg()));
+        let play_pause = if self.playing {
+            html! { <button onclick={message(|| PlaybackMsg::Pause)}>{"Pause"}</button> }
+        } else {
+            html! { <button onclick={message(|| PlaybackMsg::Play)}>{"Play"}</button> }
+        };
+        let speed_buttons = SPEEDS
+            .iter()
+            .map(|&(label, delay_ms)| {
+                let onclick =
+                    link.callback(move |_| BoardMsg::Playback(PlaybackMsg::Speed(delay_ms)));
+                html! {
+                    <button {onclick} disabled={delay_ms == self.delay_ms}
+                            style="margin-left: 4px">{label}</button>
+                }
+            })
+            .collect::<Html>();
+        html! {
+            <p style="text-align: center">
+                {"Auto-solve: "}
+                {play_pause}
+                <button onclick={message(|| PlaybackMsg::Step)}
+                        style="margin-left: 4px">{"Step"}</button>
+                <span style="margin-left: 10px">{"Speed:"}</span>
+                {speed_buttons}
+                if let Some(status) = &self.status {
+                    <br/>{status}
+                }
+            </p>
+        }
+    }
+
+    /// Highlight across the line of the last deduction, hints included
+    pub fn line_svg(&self, n_hints: usize, n_rows: usize, cell_width_px: usize) -> Html {
+        let Some(line) = self.line else {
+            return html!();
+        };
+        let (x, y, width, height) = match line {
+            Line::Row(row) => (0, n_hints + row, n_rows, 1),
+            Line::Col(col) => (n_hints + col, 0, 1, n_rows),
+        };
+        let px = |cells: usize| (cells * cell_width_px).to_string();
+        html! {
+            <rect x={px(x)} y={px(y)} width={px(width)} height={px(height)}
+                  class="playback-line"/>
+        }
+    }
+}
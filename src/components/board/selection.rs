@@ -0,0 +1,105 @@
+use crate::models::board::FieldCell;
+use crate::models::grid::Grid;
+
+use std::ops::Range;
+use yew::prelude::*;
+
+/// Rectangular block of cells, corners included
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selection {
+    top_left: (i32, i32),
+    bottom_right: (i32, i32),
+}
+
+impl Selection {
+    pub fn new(corner1: (i32, i32), corner2: (i32, i32)) -> Self {
+        Self {
+            top_left: (corner1.0.min(corner2.0), corner1.1.min(corner2.1)),
+            bottom_right: (corner1.0.max(corner2.0), corner1.1.max(corner2.1)),
+        }
+    }
+
+    /// Block covered by a picture placed with its top left corner at `top_left`
+    pub fn of_picture(top_left: (i32, i32), picture: &Grid<FieldCell>) -> Self {
+        Self {
+            top_left,
+            bottom_right: (
+                top_left.0 + picture.height() as i32 - 1,
+                top_left.1 + picture.width() as i32 - 1,
+            ),
+        }
+    }
+
+    pub fn top_left(&self) -> (i32, i32) {
+        self.top_left
+    }
+
+    pub fn contains(&self, (row, col): (i32, i32)) -> bool {
+        (self.top_left.0..=self.bottom_right.0).contains(&row)
+            && (self.top_left.1..=self.bottom_right.1).contains(&col)
+    }
+
+    pub fn moved(&self, (rows, cols): (i32, i32)) -> Self {
+        Self {
+            top_left: (self.top_left.0 + rows, self.top_left.1 + cols),
+            bottom_right: (self.bottom_right.0 + rows, self.bottom_right.1 + cols),
+        }
+    }
+
+    /// The part of the block on a square board, None if it is outside
+    pub fn clipped(&self, board_width: usize) -> Option<Self> {
+        let max = board_width as i32 - 1;
+        let clipped = Self {
+            top_left: (self.top_left.0.max(0), self.top_left.1.max(0)),
+            bottom_right: (self.bottom_right.0.min(max), self.bottom_right.1.min(max)),
+        };
+        (clipped.top_left.0 <= clipped.bottom_right.0
+            && clipped.top_left.1 <= clipped.bottom_right.1)
+            .then_some(clipped)
+    }
+
+    /// Rows of the block, which must be on the board
+    pub fn rows(&self) -> Range<usize> {
+        self.top_left.0 as usize..self.bottom_right.0 as usize + 1
+    }
+
+    /// Columns of the block, which must be on the board
+    pub fn cols(&self) -> Range<usize> {
+        self.top_left.1 as usize..self.bottom_right.1 as usize + 1
+    }
+}
+
+/// Dashed outline of a block, with the filled cells of `ghost` drawn inside
+pub fn block_svg(
+    selection: &Selection,
+    ghost: Option<&Grid<FieldCell>>,
+    n_hints: usize,
+    cell_width_px: usize,
+) -> Html {
+    let px = |cell: i32| (cell_width_px as i32 * (cell + n_hints as i32)).to_string();
+    let ghost_cells = ghost.map_or(html!(), |ghost| {
+        (0..ghost.height())
+            .flat_map(|row| (0..ghost.width()).map(move |col| (row, col)))
+            .filter(|&(row, col)| ghost[row][col] == FieldCell::Filled)
+            .map(|(row, col)| {
+                let (x, y) = (
+                    px(selection.top_left.1 + col as i32),
+                    px(selection.top_left.0 + row as i32),
+                );
+                let width = cell_width_px.to_string();
+                let height = width.clone();
+                html! { <rect {x} {y} {width} {height} class="selection-ghost"/> }
+            })
+            .collect::<Html>()
+    });
+    let width = cell_width_px as i32 * (selection.bottom_right.1 - selection.top_left.1 + 1);
+    let height = cell_width_px as i32 * (selection.bottom_right.0 - selection.top_left.0 + 1);
+    html! {
+        <>
+            {ghost_cells}
+            <rect x={px(selection.top_left.1)} y={px(selection.top_left.0)}
+                  width={width.to_string()} height={height.to_string()}
+                  class="selection-outline"/>
+        </>
+    }
+}
@@ -0,0 +1,292 @@
+use super::challenge::mistakes_key;
+use super::{stored_key, Board, BoardMsg};
+use crate::models::board::{Board as BoardModel, Progress};
+use crate::models::records::{format_duration, Record};
+use crate::storage;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use yew::html::Scope;
+use yew::prelude::*;
+
+/// Interval between updates of the time shown, in milliseconds
+const TICK_MS: i32 = 1000;
+/// Marks a stored attempt in which the auto-solver filled cells
+const ASSISTED_SUFFIX: &str = ";assisted";
+
+fn best_key(puzzle_code: &str) -> String {
+    format!("best-{puzzle_code}")
+}
+
+/// Personal bests over all unassisted solves of a puzzle
+pub fn best_record(puzzle_code: &str) -> Option<Record> {
+    Record::deserialize(&storage::load(&best_key(puzzle_code))?)
+}
+
+/// How far the player got with a puzzle in Solve mode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SolveStatus {
+    NotStarted,
+    InProgress,
+    Solved,
+}
+
+impl SolveStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SolveStatus::NotStarted => "Not started",
+            SolveStatus::InProgress => "In progress",
+            SolveStatus::Solved => "Solved",
+        }
+    }
+}
+
+/// Status of the stored progress on a puzzle, the furthest of normal solving
+/// and challenge
+pub fn solve_status(puzzle_code: &str) -> SolveStatus {
+    let fresh = BoardModel::from_serialized_solution(puzzle_code).progress();
+    [false, true]
+        .into_iter()
+        .map(|challenge| stored_key("progress", puzzle_code, challenge))
+        .filter_map(|key| storage::load(&key))
+        .filter_map(|stored| Progress::deserialize(&stored))
+        .map(|progress| {
+            let mut board = BoardModel::from_serialized_solution(puzzle_code);
+            board.restore_progress(&progress);
+            if board.is_solved() {
+                SolveStatus::Solved
+            } else if board.progress() != fresh {
+                SolveStatus::InProgress
+            } else {
+                SolveStatus::NotStarted
+            }
+        })
+        .max_by_key(|status| *status as u8)
+        .unwrap_or(SolveStatus::NotStarted)
+}
+
+/// Drop everything stored about solving a puzzle
+pub fn forget_puzzle(puzzle_code: &str) {
+    for challenge in [false, true] {
+        storage::remove(&stored_key("progress", puzzle_code, challenge));
+        storage::remove(&stored_key("stats", puzzle_code, challenge));
+    }
+    storage::remove(&mistakes_key(puzzle_code));
+    storage::remove(&best_key(puzzle_code));
+}
+
+fn now_ms() -> Option<f64> {
+    Some(web_sys::window()?.performance()?.now())
+}
+
+pub fn tab_hidden() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .is_some_and(|document| document.hidden())
+}
+
+/// Timer and counters of the current attempt at a puzzle in Solve mode
+#[derive(Default)]
+pub struct SolveStats {
+    pub record: Record,
+    /// The auto-solver filled cells, so the attempt sets no personal bests
+    pub assisted: bool,
+    /// When the timer was last started, None while it is paused
+    running_since: Option<f64>,
+    /// An update of the time shown is scheduled
+    ticking: bool,
+}
+
+impl SolveStats {
+    pub fn load(key: &str) -> Self {
+        let Some(value) = storage::load(key) else {
+            return Self::default();
+        };
+        let (value, assisted) = match value.strip_suffix(ASSISTED_SUFFIX) {
+            Some(value) => (value, true),
+            None => (value.as_str(), false),
+        };
+        Self {
+            record: Record::deserialize(value).unwrap_or_default(),
+            assisted,
+            ..Self::default()
+        }
+    }
+
+    /// Store the attempt, with the time up to now
+    pub fn store(&self, key: &str) {
+        let record = Record {
+            time_ms: self.elapsed_ms(),
+            ..self.record
+        };
+        let suffix = if self.assisted { ASSISTED_SUFFIX } else { "" };
+        storage::store(key, &(record.serialize() + suffix));
+    }
+
+    /// Merge the finished attempt into the personal bests of the puzzle
+    pub fn store_best(&self, puzzle_code: &str) {
+        if self.assisted {
+            return;
+        }
+        let best =
+            best_record(puzzle_code).map_or(self.record, |best| best.best_with(&self.record));
+        storage::store(&best_key(puzzle_code), &best.serialize());
+    }
+
+    pub fn elapsed_ms(&self) -> u64 {
+        let running_ms = match (self.running_since, now_ms()) {
+            (Some(since), Some(now)) => (now - since).max(0.) as u64,
+            _ => 0,
+        };
+        self.record.time_ms + running_ms
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    /// Start or pause the timer, returns true if it was paused or started
+    pub fn set_running(&mut self, running: bool) -> bool {
+        match (running, self.running_since) {
+            (true, None) => {
+                self.running_since = now_ms();
+                self.is_running()
+            }
+            (false, Some(_)) => {
+                self.record.time_ms = self.elapsed_ms();
+                self.running_since = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Ask for the time shown to be updated while the timer runs
+    pub fn schedule_tick(&mut self, link: &Scope<Board>) {
+        if self.ticking || !self.is_running() {
+            return;
+        }
+        let link = link.clone();
+        let tick = Closure::once_into_js(move || link.send_message(BoardMsg::TimerTick));
+        let scheduled = web_sys::window().and_then(|window| {
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    tick.unchecked_ref(),
+                    TICK_MS,
+                )
+                .ok()
+        });
+        if scheduled.is_none() {
+            log::warn!("Could not schedule a timer update");
+        }
+        self.ticking = scheduled.is_some();
+    }
+
+    /// Handle a scheduled update, returns whether the time shown changed
+    pub fn tick(&mut self) -> bool {
+        self.ticking = false;
+        self.is_running()
+    }
+
+    pub fn status(&self) -> Html {
+        html! {
+            <span>
+                {format!(
+                    "Time {} · Moves {} · Mistakes {}",
+                    format_duration(self.elapsed_ms()),
+                    self.record.moves,
+                    self.record.mistakes,
+                )}
+            </span>
+        }
+    }
+
+    /// Summary of the solved puzzle next to the personal bests, which
+    /// include this attempt
+    pub fn completion(&self, best: Option<Record>) -> Html {
+        let record = Record {
+            time_ms: self.elapsed_ms(),
+            ..self.record
+        };
+        let best = match best {
+            _ if self.assisted => best,
+            Some(best) => Some(best.best_with(&record)),
+            None => Some(record),
+        };
+        let rows = [
+            (
+                "Time",
+                record.time_ms,
+                best.map(|best| best.time_ms),
+                format_duration as fn(u64) -> String,
+            ),
+            (
+                "Moves",
+                record.moves.into(),
+                best.map(|best| best.moves.into()),
+                |n: u64| n.to_string(),
+            ),
+            (
+                "Mistakes",
+                record.mistakes.into(),
+                best.map(|best| best.mistakes.into()),
+                |n: u64| n.to_string(),
+            ),
+        ];
+        let rows = rows
+            .into_iter()
+            .map(|(label, value, best, format)| {
+                let is_best = !self.assisted && best == Some(value);
+                html! {
+                    <tr>
+                        <th>{label}</th>
+                        <td>{format(value)}</td>
+                        <td>{best.map_or("–".to_owned(), format)}</td>
+                        <td>if is_best { {"★"} }</td>
+                    </tr>
+                }
+            })
+            .collect::<Html>();
+        html! {
+            <div class="panel completion">
+                <h3>{"Solved!"}</h3>
+                <table>
+                    <tr><th/><th>{"This solve"}</th><th>{"Personal best"}</th><th/></tr>
+                    {rows}
+                </table>
+                if self.assisted {
+                    <p>{"The auto-solver helped, so this solve sets no personal bests."}</p>
+                }
+            </div>
+        }
+    }
+}
+
+/// Tells the board whenever the tab is hidden or shown again, until dropped
+pub struct VisibilityListener {
+    callback: Closure<dyn Fn()>,
+}
+
+impl VisibilityListener {
+    pub fn new(link: &Scope<Board>) -> Option<Self> {
+        let document = web_sys::window()?.document()?;
+        let link = link.clone();
+        let callback =
+            Closure::<dyn Fn()>::new(move || link.send_message(BoardMsg::VisibilityChange));
+        document
+            .add_event_listener_with_callback("visibilitychange", callback.as_ref().unchecked_ref())
+            .ok()?;
+        Some(Self { callback })
+    }
+}
+
+impl Drop for VisibilityListener {
+    fn drop(&mut self) {
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            let _ = document.remove_event_listener_with_callback(
+                "visibilitychange",
+                self.callback.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::models::board::Board as BoardModel;
+use crate::models::grid::Align;
+
+use wasm_bindgen::JsCast;
+use web_sys::EventTarget;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+const MIN_WIDTH: usize = 5;
+const MAX_WIDTH: usize = 25;
+const ALIGNS: [Align; 3] = [Align::Start, Align::Center, Align::End];
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct BoardSizeProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+    pub puzzle_width: UseStateHandle<usize>,
+}
+
+/// Width selection with the anchor kept in place on resize, cropping to the
+/// picture and adding a margin around it
+#[function_component(BoardSize)]
+pub fn board_size(props: &BoardSizeProps) -> Html {
+    let anchor = use_state(|| (Align::Start, Align::Start));
+    let width = *props.puzzle_width;
+
+    // apply an action to the board and store the result
+    let update = |action: fn(&mut BoardModel, usize, (Align, Align))| {
+        let puzzle = props.puzzle.clone();
+        let puzzle_width = props.puzzle_width.clone();
+        let anchor = *anchor;
+        move |value: usize| {
+            let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            action(&mut board, value, anchor);
+            puzzle_width.set(board.width());
+            puzzle.set(board.puzzle_code().into());
+        }
+    };
+    let width_onchange = {
+        let resize = update(|board, new_width, (vertical, horizontal)| {
+            log::info!("Resizing puzzle to width {new_width}");
+            board.resize_anchored(new_width, vertical, horizontal);
+        });
+        Callback::from(move |e: Event| {
+            let target: EventTarget = e.target().expect("Error: No target on oninput event");
+            let setval = target.unchecked_into::<HtmlInputElement>().value();
+            resize(setval.parse().expect("Could not parse width"));
+        })
+    };
+    let oncrop = {
+        let crop = update(|board, _, _| {
+            if !board.crop_to_content(MIN_WIDTH) {
+                log::info!("Nothing to crop to, the picture is empty");
+            }
+        });
+        Callback::from(move |_| crop(0))
+    };
+    let onmargin = {
+        let add_margin = update(|board, _, _| {
+            if board.width() + 2 <= MAX_WIDTH {
+                board.add_margin(1);
+            }
+        });
+        Callback::from(move |_| add_margin(0))
+    };
+    let anchor_buttons = ALIGNS
+        .iter()
+        .map(|&vertical| {
+            let row = ALIGNS
+                .iter()
+                .map(|&horizontal| {
+                    let selected = *anchor == (vertical, horizontal);
+                    let onclick = {
+                        let anchor = anchor.clone();
+                        Callback::from(move |_| anchor.set((vertical, horizontal)))
+                    };
+                    html! {
+                        <button {onclick} disabled={selected}
+                                style="width: 24px; height: 24px; padding: 0">
+                            {if selected { "●" } else { "" }}
+                        </button>
+                    }
+                })
+                .collect::<Html>();
+            html! { <div>{row}</div> }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <label for="puzzle_width_input">{"Width:"}</label>
+            <select id={"puzzle_width_input"} onchange={width_onchange}>
+                {
+                    (MIN_WIDTH..=MAX_WIDTH).map(|w| html!{
+                        <option
+                            value={w.to_string()}
+                            selected={w == width}>
+                           {w.to_string()}
+                        </option>
+                    }).collect::<Html>()
+                }
+            </select>
+            <div style="display: flex; align-items: center; margin-top: 4px">
+                <span style="margin-right: 4px">{"Anchor:"}</span>
+                <div title="Part of the picture kept in place when resizing">
+                    {anchor_buttons}
+                </div>
+                <button onclick={oncrop} style="margin-left: 10px">{"Crop to picture"}</button>
+                <button onclick={onmargin} style="margin-left: 4px">{"Add margin"}</button>
+            </div>
+        </>
+    }
+}
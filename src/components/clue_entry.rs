@@ -0,0 +1,164 @@
+use crate::models::board::FieldCell;
+use crate::models::clues::Clues;
+use crate::models::grid::Grid;
+use crate::models::solver::solutions;
+use crate::routes::Route;
+
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ClueEntryProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+    pub puzzle_width: UseStateHandle<usize>,
+}
+
+#[derive(Clone, PartialEq)]
+enum Outcome {
+    Failed(String),
+    /// None if the search for another solution gave up
+    Solved {
+        unique: Option<bool>,
+    },
+}
+
+/// Panel to type in row and column clues and have the solution worked out
+#[function_component(ClueEntry)]
+pub fn clue_entry(props: &ClueEntryProps) -> Html {
+    let width = *props.puzzle_width;
+    let row_texts = use_state(|| vec![String::new(); width]);
+    let col_texts = use_state(|| vec![String::new(); width]);
+    let outcome = use_state(|| Option::<Outcome>::None);
+
+    let width_onchange = {
+        let (row_texts, col_texts) = (row_texts.clone(), col_texts.clone());
+        let (puzzle, puzzle_width) = (props.puzzle.clone(), props.puzzle_width.clone());
+        let outcome = outcome.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let new_width = select.value().parse().expect("Could not parse width");
+            let resized = |texts: &Vec<String>| {
+                let mut texts = texts.clone();
+                texts.resize(new_width, String::new());
+                texts
+            };
+            row_texts.set(resized(&row_texts));
+            col_texts.set(resized(&col_texts));
+            puzzle_width.set(new_width);
+            puzzle.set(
+                Grid::<FieldCell>::new(new_width, new_width)
+                    .serialize_base64()
+                    .into(),
+            );
+            outcome.set(None);
+        })
+    };
+    let text_oninput = |texts: &UseStateHandle<Vec<String>>, idx: usize| {
+        let texts = texts.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut new_texts = (*texts).clone();
+            new_texts[idx] = input.value();
+            texts.set(new_texts);
+        })
+    };
+    let onsolve = {
+        let (row_texts, col_texts) = (row_texts.clone(), col_texts.clone());
+        let puzzle = props.puzzle.clone();
+        let outcome = outcome.clone();
+        Callback::from(move |_| {
+            let clues = Clues::parse(row_texts.as_slice(), col_texts.as_slice());
+            let clues = match clues.and_then(|clues| clues.validate().map(|_| clues)) {
+                Ok(clues) => clues,
+                Err(e) => {
+                    outcome.set(Some(Outcome::Failed(e.to_string())));
+                    return;
+                }
+            };
+            let found = solutions(&clues, 2);
+            match found.found.first() {
+                None if found.gave_up => outcome.set(Some(Outcome::Failed(
+                    "These clues leave too much open, the search gave up".to_owned(),
+                ))),
+                None => outcome.set(Some(Outcome::Failed(
+                    "These clues have no solution".to_owned(),
+                ))),
+                Some(solution) => {
+                    log::info!(
+                        "Solved entered clues, {} solution(s) found",
+                        found.found.len()
+                    );
+                    puzzle.set(solution.serialize_base64().into());
+                    outcome.set(Some(Outcome::Solved {
+                        unique: found.unique(),
+                    }));
+                }
+            }
+        })
+    };
+
+    let clue_inputs = |name: &str, texts: &UseStateHandle<Vec<String>>| {
+        texts
+            .iter()
+            .enumerate()
+            .map(|(idx, text)| {
+                let id = format!("clue_{name}_{idx}_inp");
+                html! {
+                    <p>
+                        <label for={id.clone()}>{format!("{name} {}:", idx + 1)}</label>
+                        <input {id} value={text.clone()} placeholder="3 1 2"
+                               oninput={text_oninput(texts, idx)}/>
+                    </p>
+                }
+            })
+            .collect::<Html>()
+    };
+    let outcome_html = match &*outcome {
+        None => html! {},
+        Some(Outcome::Failed(message)) => html! { <p>{message}</p> },
+        Some(Outcome::Solved { unique }) => html! {
+            <p>
+                {match unique {
+                    Some(true) => "",
+                    Some(false) => {
+                        "The clues have more than one solution, showing one of them. "
+                    }
+                    None => "The search gave up before telling whether the solution \
+                             is unique. ",
+                }}
+                <Link<Route> to={Route::Solve{puzzle: props.puzzle.to_string()}}>
+                    {"Play"}
+                </Link<Route>>
+                {" or "}
+                <Link<Route> to={Route::Set{puzzle: props.puzzle.to_string()}}>
+                    {"edit and share"}
+                </Link<Route>>
+            </p>
+        },
+    };
+
+    html! {
+        <div style={"display:flex"}>
+        <div class="panel">
+            <h3>{"Enter Clues"}</h3>
+            <label for="clue_width_inp">{"Width:"}</label>
+            <select id={"clue_width_inp"} onchange={width_onchange}>
+                {
+                    (5..=25).map(|w| html!{
+                        <option value={w.to_string()} selected={w == width}>
+                           {w.to_string()}
+                        </option>
+                    }).collect::<Html>()
+                }
+            </select>
+            <div style={"display:flex"}>
+                <div>{clue_inputs("Row", &row_texts)}</div>
+                <div>{clue_inputs("Column", &col_texts)}</div>
+            </div>
+            <button onclick={onsolve}>{"Solve"}</button>
+            {outcome_html}
+        </div>
+        </div>
+    }
+}
@@ -1,6 +1,5 @@
 use wasm_bindgen::JsCast;
-use web_sys::HtmlDocument;
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlDocument, HtmlTextAreaElement};
 use yew::prelude::*;
 
 #[derive(Properties, Clone, PartialEq)]
@@ -12,29 +11,39 @@ pub struct CopyToClipboardProps {
     pub button_id: AttrValue,
 }
 
+/// Put text on the system clipboard
+pub fn copy_text(text: &str) {
+    let document = web_sys::window()
+        .expect("Could not get window")
+        .document()
+        .expect("Could not get document")
+        .dyn_into::<HtmlDocument>()
+        .expect("Could not cast document to HtmlDocument");
+    let body = document.body().expect("Could not get body");
+    let textarea = document
+        .create_element("textarea")
+        .expect("Could not create textarea")
+        .unchecked_into::<HtmlTextAreaElement>();
+    textarea.set_value(text);
+    if body.append_child(&textarea).is_err() {
+        log::warn!("Could not add textarea to copy from");
+        return;
+    }
+    textarea.select();
+    // FIXME: Use clipboard API once stabilized in web_sys
+    let result = document.exec_command("copy");
+    log::info!(
+        "Copied to clipboard with return value '{}'",
+        result.unwrap_or(false)
+    );
+    textarea.remove();
+}
+
 #[function_component(CopyToClipboard)]
 pub fn copy_to_clipboard(props: &CopyToClipboardProps) -> Html {
-    let input_ref = use_node_ref();
     let onclick = {
-        let input_ref = input_ref.clone();
-        Callback::from(move |_| {
-            let input = input_ref
-                .cast::<HtmlInputElement>()
-                .expect("Could not get input element of CopyToClipboard component");
-            input.select();
-            let document = web_sys::window()
-                .expect("Could not get window")
-                .document()
-                .expect("Could not get document")
-                .dyn_into::<HtmlDocument>()
-                .expect("Could not cast document to HtmlDocument");
-            // FIXME: Use clipboard API once stabilized in web_sys
-            let result = document.exec_command("copy");
-            log::info!(
-                "Copied to clipboard with return value '{}'",
-                result.unwrap_or(false)
-            );
-        })
+        let value = props.value.clone();
+        Callback::from(move |_| copy_text(&value))
     };
 
     html! {
@@ -42,7 +51,6 @@ pub fn copy_to_clipboard(props: &CopyToClipboardProps) -> Html {
             <input
                 type={"text"}
                 id={props.input_id.clone()}
-                ref={input_ref}
                 value={props.value.clone()}
                 readonly={true}
             />
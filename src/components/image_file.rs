@@ -0,0 +1,70 @@
+//! Image files picked by the user, decoded by the browser
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, File, HtmlCanvasElement, HtmlImageElement, Url};
+
+/// Draw the image onto a new canvas, scaled down to at most `max_px` on its
+/// longer side
+fn draw_scaled(image: &HtmlImageElement, max_px: u32) -> Option<CanvasRenderingContext2d> {
+    let (width, height) = (image.natural_width(), image.natural_height());
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let scale = (max_px as f64 / width.max(height) as f64).min(1.0);
+    let width = ((width as f64 * scale) as u32).max(1);
+    let height = ((height as f64 * scale) as u32).max(1);
+    let canvas = web_sys::window()?
+        .document()?
+        .create_element("canvas")
+        .ok()?
+        .unchecked_into::<HtmlCanvasElement>();
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context = canvas
+        .get_context("2d")
+        .ok()??
+        .unchecked_into::<CanvasRenderingContext2d>();
+    context
+        .draw_image_with_html_image_element_and_dw_and_dh(
+            image,
+            0.0,
+            0.0,
+            width as f64,
+            height as f64,
+        )
+        .ok()?;
+    Some(context)
+}
+
+/// Let the browser decode the image file, hand it over drawn on a canvas of at
+/// most `max_px` on its longer side once loaded. Files the browser cannot
+/// decode are only logged
+pub fn load_image(
+    file: &File,
+    max_px: u32,
+    on_loaded: impl FnOnce(CanvasRenderingContext2d) + 'static,
+) {
+    let url = Url::create_object_url_with_blob(file).expect("Could not create object URL");
+    let image = HtmlImageElement::new().expect("Could not create image element");
+    let onload = {
+        let (image, url) = (image.clone(), url.clone());
+        Closure::once_into_js(move || {
+            let _ = Url::revoke_object_url(&url);
+            match draw_scaled(&image, max_px) {
+                Some(context) => on_loaded(context),
+                None => log::warn!("Could not draw uploaded image"),
+            }
+        })
+    };
+    let onerror = {
+        let (name, url) = (file.name(), url.clone());
+        Closure::once_into_js(move || {
+            let _ = Url::revoke_object_url(&url);
+            log::warn!("Could not decode uploaded image '{name}'");
+        })
+    };
+    image.set_onload(Some(onload.unchecked_ref()));
+    image.set_onerror(Some(onerror.unchecked_ref()));
+    image.set_src(&url);
+}
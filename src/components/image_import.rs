@@ -0,0 +1,165 @@
+use crate::components::board::NonogramPreview;
+use crate::components::image_file::load_image;
+use crate::models::bitmap::Bitmap;
+use crate::models::board::Board as BoardModel;
+
+use std::rc::Rc;
+use web_sys::{File, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+// larger images are scaled down by the browser before we look at the pixels
+const MAX_SOURCE_PX: u32 = 512;
+const PREVIEW_PX: u32 = 150;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ImageImportProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+    pub puzzle_width: UseStateHandle<usize>,
+}
+
+/// Let the browser decode the image file, emit its pixels once loaded
+fn load_bitmap(file: &File, on_loaded: Callback<Bitmap>) {
+    load_image(file, MAX_SOURCE_PX, move |context| {
+        let Some(canvas) = context.canvas() else {
+            return;
+        };
+        let (width, height) = (canvas.width(), canvas.height());
+        match context.get_image_data(0.0, 0.0, width as f64, height as f64) {
+            Ok(data) => on_loaded.emit(Bitmap::new(width as usize, height as usize, data.data().0)),
+            Err(_) => log::warn!("Could not read pixels of uploaded image"),
+        }
+    });
+}
+
+/// Dialog to turn an uploaded picture into the solution
+#[function_component(ImageImport)]
+pub fn image_import(props: &ImageImportProps) -> Html {
+    let open = use_state(|| false);
+    let bitmap = use_state(|| Option::<Rc<Bitmap>>::None);
+    let target_width = use_state(|| *props.puzzle_width);
+    let target_height = use_state(|| *props.puzzle_width);
+    let threshold = use_state(|| 128u8);
+    let dither = use_state(|| false);
+
+    let toggle = {
+        let (open, bitmap) = (open.clone(), bitmap.clone());
+        Callback::from(move |_| {
+            bitmap.set(None);
+            open.set(!*open);
+        })
+    };
+    let file_onchange = {
+        let bitmap = bitmap.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let bitmap = bitmap.clone();
+                load_bitmap(&file, Callback::from(move |b| bitmap.set(Some(Rc::new(b)))));
+            }
+        })
+    };
+    let size_onchange = |state: &UseStateHandle<usize>| {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            state.set(select.value().parse().expect("Could not parse size"));
+        })
+    };
+    let threshold_oninput = {
+        let threshold = threshold.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            threshold.set(input.value().parse().unwrap_or(128));
+        })
+    };
+    let dither_onchange = {
+        let dither = dither.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            dither.set(input.checked());
+        })
+    };
+
+    let preview = bitmap.as_ref().map(|bitmap| {
+        let grid = bitmap.to_grid(*target_width, *target_height, *threshold, *dither);
+        BoardModel::from_picture(&grid)
+    });
+    let onapply = {
+        let (open, bitmap) = (open.clone(), bitmap.clone());
+        let solution = preview.as_ref().map(|board| board.solution_ref().clone());
+        let puzzle = props.puzzle.clone();
+        let puzzle_width = props.puzzle_width.clone();
+        Callback::from(move |_| {
+            if let Some(solution) = &solution {
+                log::info!(
+                    "Importing {}x{} picture from image",
+                    solution.width(),
+                    solution.height()
+                );
+                puzzle_width.set(solution.width());
+                let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+                board.set_picture(solution);
+                puzzle.set(board.puzzle_code().into());
+            }
+            bitmap.set(None);
+            open.set(false);
+        })
+    };
+    let size_select = |id: &'static str, state: &UseStateHandle<usize>| {
+        html! {
+            <select {id} onchange={size_onchange(state)}>
+                {
+                    (5..=25).map(|w| html!{
+                        <option value={w.to_string()} selected={w == **state}>
+                            {w.to_string()}
+                        </option>
+                    }).collect::<Html>()
+                }
+            </select>
+        }
+    };
+
+    html! {
+        <>
+            <button onclick={toggle.clone()} style="margin-left: 4px">{"Import image"}</button>
+            if *open {
+                <div class="panel">
+                    <p>
+                        <label for="image_file_inp">{"Image (PNG, JPEG):"}</label>
+                        <input id="image_file_inp" type="file"
+                               accept="image/png, image/jpeg" onchange={file_onchange}/>
+                    </p>
+                    <p>
+                        <label for="image_width_inp">{"Width:"}</label>
+                        {size_select("image_width_inp", &target_width)}
+                    </p>
+                    <p>
+                        <label for="image_height_inp">{"Height:"}</label>
+                        {size_select("image_height_inp", &target_height)}
+                    </p>
+                    <p>
+                        <label for="image_threshold_inp">{"Threshold:"}</label>
+                        <input id="image_threshold_inp" type="range" min="0" max="255"
+                               value={threshold.to_string()} oninput={threshold_oninput}/>
+                    </p>
+                    <p>
+                        <label for="image_dither_inp">{"Dithering:"}</label>
+                        <input id="image_dither_inp" type="checkbox"
+                               checked={*dither} onchange={dither_onchange}/>
+                    </p>
+                    if let Some(board) = &preview {
+                        <p>
+                            <svg width={PREVIEW_PX.to_string()} height={PREVIEW_PX.to_string()}>
+                                <NonogramPreview field={board.solution_ref().clone()}
+                                                 width_px={PREVIEW_PX}
+                                                 margin_px={0}/>
+                            </svg>
+                        </p>
+                    }
+                    <button onclick={onapply} disabled={preview.is_none()}>{"Use picture"}</button>
+                    <button onclick={toggle} style="margin-left: 4px">{"Cancel"}</button>
+                </div>
+            }
+        </>
+    }
+}
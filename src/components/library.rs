@@ -0,0 +1,252 @@
+use crate::components::board::{
+    best_record, download_text, forget_puzzle, solve_status, NonogramPreview, SolveStatus,
+};
+use crate::models::board::Board as BoardModel;
+use crate::models::library::{EntryKind, Library, LibraryEntry};
+use crate::models::records::format_duration;
+use crate::routes::{self, Route};
+use crate::storage;
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+const LIBRARY_KEY: &str = "library";
+const THUMBNAIL_PX: u32 = 60;
+
+pub fn load() -> Library {
+    storage::load(LIBRARY_KEY)
+        .map(|stored| Library::deserialize(&stored))
+        .unwrap_or_default()
+}
+
+fn store(library: &Library) {
+    storage::store(LIBRARY_KEY, &library.serialize());
+}
+
+/// Apply a change to the stored library, storing it if anything changed
+pub fn update(change: impl FnOnce(&mut Library) -> bool) {
+    let mut library = load();
+    if change(&mut library) {
+        store(&library);
+    }
+}
+
+fn entry_route(entry: &LibraryEntry) -> Route {
+    let puzzle = entry.code.clone();
+    match entry.kind {
+        EntryKind::Draft => Route::SetDraft {
+            puzzle,
+            draft: entry.id,
+        },
+        EntryKind::Played => Route::Solve { puzzle },
+    }
+}
+
+/// Name and links of an entry, to keep them outside the browser
+fn export_text(entry: &LibraryEntry, navigator: &Navigator) -> String {
+    let solve = routes::to_href(
+        navigator,
+        &Route::Solve {
+            puzzle: entry.code.clone(),
+        },
+    );
+    match entry.kind {
+        EntryKind::Draft => {
+            let set = routes::to_href(
+                navigator,
+                &Route::Set {
+                    puzzle: entry.code.clone(),
+                },
+            );
+            format!("{}\nSolve: {solve}\nContinue setting: {set}\n", entry.name)
+        }
+        EntryKind::Played => format!("{}\nSolve: {solve}\n", entry.name),
+    }
+}
+
+fn file_name(name: &str) -> String {
+    let stem = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    format!("{}.txt", stem.trim_matches('-'))
+}
+
+/// Picture of the puzzle, hidden until it is solved unless the player set it
+fn thumbnail(entry: &LibraryEntry, board: &BoardModel, status: SolveStatus) -> Html {
+    let size = THUMBNAIL_PX.to_string();
+    let content = if entry.kind == EntryKind::Draft || status == SolveStatus::Solved {
+        html! {
+            <NonogramPreview field={board.solution_ref().clone()}
+                             width_px={THUMBNAIL_PX} margin_px={0}/>
+        }
+    } else {
+        let center = (THUMBNAIL_PX / 2).to_string();
+        html! {
+            <>
+                <rect x="0" y="0" width={size.clone()} height={size.clone()}
+                      class="library-thumbnail-hidden"/>
+                <text x={center.clone()} y={center} text-anchor="middle"
+                      dominant-baseline="middle">{"?"}</text>
+            </>
+        }
+    };
+    html! { <svg width={size.clone()} height={size}>{content}</svg> }
+}
+
+/// Puzzles set or played on this device, to reopen and manage them
+#[function_component(PuzzleLibrary)]
+pub fn puzzle_library() -> Html {
+    let library = use_state(load);
+    let renaming = use_state(|| Option::<usize>::None);
+    let rename_ref = use_node_ref();
+    let navigator = use_navigator().expect("Failed to get navigator");
+
+    let onduplicate = |idx: usize| {
+        let library = library.clone();
+        Callback::from(move |_| {
+            let mut changed = (*library).clone();
+            changed.duplicate(idx);
+            store(&changed);
+            library.set(changed);
+        })
+    };
+    let ondelete = |idx: usize| {
+        let library = library.clone();
+        Callback::from(move |_| {
+            let mut changed = (*library).clone();
+            let Some(entry) = changed.entries.get(idx) else {
+                return;
+            };
+            let confirmed = web_sys::window()
+                .and_then(|window| {
+                    let question = format!("Delete \"{}\" from the library?", entry.name);
+                    window.confirm_with_message(&question).ok()
+                })
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+            if let Some(entry) = changed.remove(idx) {
+                if !changed.contains_code(&entry.code) {
+                    forget_puzzle(&entry.code);
+                }
+            }
+            store(&changed);
+            library.set(changed);
+        })
+    };
+    let onrename = |idx: usize| {
+        let renaming = renaming.clone();
+        Callback::from(move |_| renaming.set(Some(idx)))
+    };
+    let onsave_name = {
+        let (library, renaming, rename_ref) =
+            (library.clone(), renaming.clone(), rename_ref.clone());
+        Callback::from(move |_| {
+            let (Some(idx), Some(input)) = (*renaming, rename_ref.cast::<HtmlInputElement>())
+            else {
+                return;
+            };
+            let mut changed = (*library).clone();
+            if changed.rename(idx, &input.value()) {
+                store(&changed);
+                library.set(changed);
+            }
+            renaming.set(None);
+        })
+    };
+    let oncancel_rename = {
+        let renaming = renaming.clone();
+        Callback::from(move |_| renaming.set(None))
+    };
+    let onexport = |idx: usize| {
+        let (library, navigator) = (library.clone(), navigator.clone());
+        Callback::from(move |_| {
+            if let Some(entry) = library.entries.get(idx) {
+                download_text(&export_text(entry, &navigator), &file_name(&entry.name));
+            }
+        })
+    };
+    let onexport_all = {
+        let (library, navigator) = (library.clone(), navigator.clone());
+        Callback::from(move |_| {
+            let text = library
+                .entries
+                .iter()
+                .map(|entry| export_text(entry, &navigator))
+                .collect::<Vec<_>>()
+                .join("\n");
+            download_text(&text, "nonogram-library.txt");
+        })
+    };
+
+    let rows = library
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            let board = BoardModel::from_serialized_solution(&entry.code);
+            let status = solve_status(&entry.code);
+            let best_time = best_record(&entry.code)
+                .map_or("–".to_owned(), |best| format_duration(best.time_ms));
+            let name = if *renaming == Some(idx) {
+                html! {
+                    <>
+                        <input ref={rename_ref.clone()} value={entry.name.clone()}/>
+                        <button onclick={onsave_name.clone()}
+                                style="margin-left: 4px">{"Save"}</button>
+                        <button onclick={oncancel_rename.clone()}
+                                style="margin-left: 4px">{"Cancel"}</button>
+                    </>
+                }
+            } else {
+                html! { {&entry.name} }
+            };
+            html! {
+                <tr>
+                    <td>{thumbnail(entry, &board, status)}</td>
+                    <td>{name}<br/><small>{entry.kind.label()}</small></td>
+                    <td>{format!("{0}×{0}", board.width())}</td>
+                    <td>{status.label()}</td>
+                    <td>{best_time}</td>
+                    <td>
+                        <Link<Route> to={entry_route(entry)}>{"Open"}</Link<Route>>
+                        <button onclick={onrename(idx)}
+                                style="margin-left: 4px">{"Rename"}</button>
+                        <button onclick={onduplicate(idx)}
+                                style="margin-left: 4px">{"Duplicate"}</button>
+                        <button onclick={ondelete(idx)}
+                                style="margin-left: 4px">{"Delete"}</button>
+                        <button onclick={onexport(idx)}
+                                style="margin-left: 4px">{"Export"}</button>
+                    </td>
+                </tr>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="panel library">
+            <h3>{"My Puzzles"}</h3>
+            if library.entries.is_empty() {
+                <p>{"Puzzles you set or solve on this device are listed here."}</p>
+            } else {
+                <table>
+                    <tr>
+                        <th/><th>{"Name"}</th><th>{"Size"}</th><th>{"Status"}</th>
+                        <th>{"Best time"}</th><th/>
+                    </tr>
+                    {rows}
+                </table>
+                <p>
+                    <button onclick={onexport_all}>{"Export all"}</button>
+                </p>
+            }
+            <p>
+                <Link<Route> to={Route::SetNew}>{"Set a new puzzle"}</Link<Route>>
+            </p>
+        </div>
+    }
+}
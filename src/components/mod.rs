@@ -1,3 +1,16 @@
 pub mod board;
 pub mod setting_panel;
 pub mod copy_to_clipboard;
+pub mod picture_text;
+pub mod image_file;
+pub mod image_import;
+pub mod text_to_picture;
+pub mod print_view;
+pub mod clue_entry;
+pub mod picture_transforms;
+pub mod board_size;
+pub mod underlay;
+pub mod uniqueness_check;
+pub mod alternative_solutions;
+pub mod library;
+pub mod puzzle_details;
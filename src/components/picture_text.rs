@@ -0,0 +1,181 @@
+use crate::models::ascii_art::{RaggedLines, TextFormat};
+use crate::models::board::{Board as BoardModel, FieldCell};
+use crate::models::grid::Grid;
+
+use web_sys::HtmlInputElement;
+use web_sys::HtmlSelectElement;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+const MIN_WIDTH: usize = 5;
+const MAX_WIDTH: usize = 25;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct PictureTextProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+    pub puzzle_width: UseStateHandle<usize>,
+}
+
+fn format_char(input: &NodeRef, default: char) -> char {
+    input
+        .cast::<HtmlInputElement>()
+        .and_then(|input| input.value().chars().next())
+        .unwrap_or(default)
+}
+
+/// Dialog to replace the solution with a picture pasted as text
+#[function_component(PastePicture)]
+pub fn paste_picture(props: &PictureTextProps) -> Html {
+    let open = use_state(|| false);
+    let error = use_state(|| Option::<String>::None);
+    let text_ref = use_node_ref();
+    let filled_ref = use_node_ref();
+    let empty_ref = use_node_ref();
+    let trim_ref = use_node_ref();
+    let ragged_ref = use_node_ref();
+
+    let toggle = {
+        let open = open.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            error.set(None);
+            open.set(!*open);
+        })
+    };
+    let onimport = {
+        let (open, error) = (open.clone(), error.clone());
+        let (text_ref, filled_ref, empty_ref, trim_ref, ragged_ref) = (
+            text_ref.clone(),
+            filled_ref.clone(),
+            empty_ref.clone(),
+            trim_ref.clone(),
+            ragged_ref.clone(),
+        );
+        let puzzle = props.puzzle.clone();
+        let puzzle_width = props.puzzle_width.clone();
+        Callback::from(move |_| {
+            let default = TextFormat::default();
+            let format = TextFormat {
+                filled: format_char(&filled_ref, default.filled),
+                empty: format_char(&empty_ref, default.empty),
+                trim: trim_ref
+                    .cast::<HtmlInputElement>()
+                    .map_or(default.trim, |input| input.checked()),
+                ragged: match ragged_ref
+                    .cast::<HtmlSelectElement>()
+                    .map(|select| select.value())
+                    .as_deref()
+                {
+                    Some("reject") => RaggedLines::Reject,
+                    _ => RaggedLines::Pad,
+                },
+            };
+            let text = text_ref
+                .cast::<HtmlTextAreaElement>()
+                .expect("Could not get picture text area")
+                .value();
+            let grid = match Grid::<FieldCell>::from_text(&text, &format) {
+                Ok(grid) => grid,
+                Err(e) => {
+                    error.set(Some(e.to_string()));
+                    return;
+                }
+            };
+            let new_width = std::cmp::max(grid.width(), grid.height());
+            if new_width > MAX_WIDTH {
+                error.set(Some(format!(
+                    "Picture is {}x{}, at most {MAX_WIDTH}x{MAX_WIDTH} is supported",
+                    grid.width(),
+                    grid.height()
+                )));
+                return;
+            }
+            let new_width = std::cmp::max(new_width, MIN_WIDTH);
+            log::info!(
+                "Importing {}x{} picture from text",
+                grid.width(),
+                grid.height()
+            );
+            let grid = grid.resized(new_width, new_width);
+            puzzle_width.set(new_width);
+            let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            board.set_picture(&grid);
+            puzzle.set(board.puzzle_code().into());
+            error.set(None);
+            open.set(false);
+        })
+    };
+
+    html! {
+        <>
+            <button onclick={toggle.clone()}>{"Paste picture"}</button>
+            if *open {
+                <div class="panel">
+                    <p>
+                        <textarea ref={text_ref} rows="12" cols="30"
+                                  placeholder={"..##..\n.####.\n######"}/>
+                    </p>
+                    <p>
+                        <label for="paste_filled_inp">{"Filled character:"}</label>
+                        <input id="paste_filled_inp" ref={filled_ref} maxlength="1"
+                               value={TextFormat::default().filled.to_string()}/>
+                    </p>
+                    <p>
+                        <label for="paste_empty_inp">{"Empty character:"}</label>
+                        <input id="paste_empty_inp" ref={empty_ref} maxlength="1"
+                               value={TextFormat::default().empty.to_string()}/>
+                    </p>
+                    <p>
+                        <label for="paste_trim_inp">{"Trim blank border:"}</label>
+                        <input id="paste_trim_inp" ref={trim_ref} type="checkbox"
+                               checked={TextFormat::default().trim}/>
+                    </p>
+                    <p>
+                        <label for="paste_ragged_inp">{"Short lines:"}</label>
+                        <select id="paste_ragged_inp" ref={ragged_ref}>
+                            <option value="pad" selected={true}>{"Pad with empty"}</option>
+                            <option value="reject">{"Reject"}</option>
+                        </select>
+                    </p>
+                    if let Some(error) = &*error {
+                        <p>{error}</p>
+                    }
+                    <button onclick={onimport}>{"Import"}</button>
+                    <button onclick={toggle} style="margin-left: 4px">{"Cancel"}</button>
+                </div>
+            }
+        </>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ExportTextProps {
+    pub puzzle: AttrValue,
+}
+
+/// Button revealing the solution as text, drawn with the default characters
+#[function_component(ExportText)]
+pub fn export_text(props: &ExportTextProps) -> Html {
+    let open = use_state(|| false);
+    let toggle = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(!*open))
+    };
+    let text = if *open {
+        let board = BoardModel::from_serialized_solution(props.puzzle.as_ref());
+        board.solution_ref().to_text(&TextFormat::default())
+    } else {
+        String::new()
+    };
+    let rows = text.lines().count().max(1).to_string();
+    html! {
+        <>
+            <button onclick={toggle} style="margin-left: 4px">{"Export as text"}</button>
+            if *open {
+                <div class="panel">
+                    <textarea readonly={true} {rows} cols="30" value={text}/>
+                </div>
+            }
+        </>
+    }
+}
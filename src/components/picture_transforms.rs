@@ -0,0 +1,64 @@
+use crate::models::board::{Board as BoardModel, FieldCell};
+use crate::models::grid::Grid;
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct PictureTransformsProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+}
+
+/// Buttons to flip, rotate, shift or invert the whole solution
+#[function_component(PictureTransforms)]
+pub fn picture_transforms(props: &PictureTransformsProps) -> Html {
+    let cyclic = use_state(|| true);
+
+    let apply = |transform: fn(&Grid<FieldCell>, bool) -> Grid<FieldCell>| {
+        let puzzle = props.puzzle.clone();
+        let cyclic = *cyclic;
+        Callback::from(move |_| {
+            let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            board.transform(|solution| transform(solution, cyclic));
+            puzzle.set(board.puzzle_code().into());
+        })
+    };
+    let cyclic_onchange = {
+        let cyclic = cyclic.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            cyclic.set(input.checked());
+        })
+    };
+    let button = |label: &str, title: &str, onclick: Callback<MouseEvent>| {
+        html! {
+            <button {onclick} title={title.to_owned()}
+                    style="margin-right: 4px">{label.to_owned()}</button>
+        }
+    };
+
+    html! {
+        <>
+            <p>
+                {"Picture: "}
+                {button("⇆", "Flip horizontally", apply(|grid, _| grid.flipped_horizontally()))}
+                {button("⇅", "Flip vertically", apply(|grid, _| grid.flipped_vertically()))}
+                {button("↻", "Rotate clockwise", apply(|grid, _| grid.rotated_clockwise()))}
+                {button("↺", "Rotate counterclockwise",
+                        apply(|grid, _| grid.rotated_counterclockwise()))}
+                {button("⤡", "Transpose", apply(|grid, _| grid.transposed()))}
+                {button("Invert", "Swap filled and empty cells", apply(|grid, _| grid.inverted()))}
+            </p>
+            <p>
+                {"Shift: "}
+                {button("←", "Shift left", apply(|grid, cyclic| grid.shifted(0, -1, cyclic)))}
+                {button("→", "Shift right", apply(|grid, cyclic| grid.shifted(0, 1, cyclic)))}
+                {button("↑", "Shift up", apply(|grid, cyclic| grid.shifted(-1, 0, cyclic)))}
+                {button("↓", "Shift down", apply(|grid, cyclic| grid.shifted(1, 0, cyclic)))}
+                <label for="shift_cyclic_inp">{"Wrap around:"}</label>
+                <input id="shift_cyclic_inp" type="checkbox"
+                       checked={*cyclic} onchange={cyclic_onchange}/>
+            </p>
+        </>
+    }
+}
@@ -0,0 +1,217 @@
+use crate::components::board::{sheet_svg, SheetContent};
+use crate::models::board::Board as BoardModel;
+use crate::routes::Route;
+
+use itertools::Itertools;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+const PUZZLE_SEPARATOR: char = ',';
+const ANSWERS_PER_PAGE: usize = 6;
+const ANSWER_WIDTH_PX: usize = 220;
+
+/// Board size for a given number of puzzles on an A4/letter page
+fn puzzle_width_px(per_page: usize) -> usize {
+    match per_page {
+        1 => 640,
+        2 => 440,
+        _ => 320,
+    }
+}
+
+/// Accept either a puzzle code or a link to a puzzle
+fn puzzle_code_from_input(input: &str) -> Option<String> {
+    let code = input.trim().trim_end_matches('/').rsplit('/').next()?;
+    (!code.is_empty() && !code.contains(PUZZLE_SEPARATOR)).then(|| code.to_owned())
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct PrintViewProps {
+    /// Puzzle codes separated by commas
+    pub puzzles: AttrValue,
+}
+
+#[function_component(PrintView)]
+pub fn print_view(props: &PrintViewProps) -> Html {
+    let per_page = use_state(|| 1usize);
+    let answer_key = use_state(|| true);
+    let add_ref = use_node_ref();
+    let navigator = use_navigator().expect("Failed to get navigator");
+
+    let codes = props
+        .puzzles
+        .split(PUZZLE_SEPARATOR)
+        .filter(|code| !code.is_empty())
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let boards = codes
+        .iter()
+        .map(|code| BoardModel::from_serialized_solution(code))
+        .collect::<Vec<_>>();
+
+    let navigate_to = {
+        let navigator = navigator.clone();
+        move |codes: Vec<String>| {
+            navigator.replace(&Route::Print {
+                puzzles: codes.join(&PUZZLE_SEPARATOR.to_string()),
+            })
+        }
+    };
+    let per_page_onchange = {
+        let per_page = per_page.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            per_page.set(select.value().parse().unwrap_or(1));
+        })
+    };
+    let answer_key_onchange = {
+        let answer_key = answer_key.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            answer_key.set(input.checked());
+        })
+    };
+    let onadd = {
+        let (codes, add_ref, navigate_to) = (codes.clone(), add_ref.clone(), navigate_to.clone());
+        Callback::from(move |_| {
+            let input = add_ref
+                .cast::<HtmlInputElement>()
+                .expect("Could not get puzzle input");
+            if let Some(code) = puzzle_code_from_input(&input.value()) {
+                let mut codes = codes.clone();
+                codes.push(code);
+                input.set_value("");
+                navigate_to(codes);
+            }
+        })
+    };
+    let remove = |idx: usize| {
+        let (codes, navigate_to) = (codes.clone(), navigate_to.clone());
+        Callback::from(move |_| {
+            let mut codes = codes.clone();
+            codes.remove(idx);
+            navigate_to(codes);
+        })
+    };
+    let onprint = Callback::from(|_| {
+        let window = web_sys::window().expect("Could not get window");
+        if window.print().is_err() {
+            log::warn!("Could not open print dialog");
+        }
+    });
+
+    let width_px = puzzle_width_px(*per_page);
+    let puzzle_pages = boards
+        .iter()
+        .enumerate()
+        .chunks(*per_page)
+        .into_iter()
+        .map(|page| {
+            let puzzles = page
+                .map(|(idx, board)| {
+                    html! {
+                        <div class="print-puzzle">
+                            <h3>
+                                {format!("Puzzle {}", idx + 1)}
+                                <button class="no-print" onclick={remove(idx)}
+                                        style="margin-left: 10px">{"Remove"}</button>
+                            </h3>
+                            <svg class="print-board"
+                                 width={width_px.to_string()} height={width_px.to_string()}>
+                                {sheet_svg(board, SheetContent::Clues, None, width_px)}
+                            </svg>
+                        </div>
+                    }
+                })
+                .collect::<Html>();
+            html! { <div class="print-page">{puzzles}</div> }
+        })
+        .collect::<Html>();
+    let answer_pages = if *answer_key {
+        boards
+            .iter()
+            .enumerate()
+            .chunks(ANSWERS_PER_PAGE)
+            .into_iter()
+            .map(|page| {
+                let answers = page
+                    .map(|(idx, board)| {
+                        html! {
+                            <div class="print-puzzle">
+                                <h3>{format!("Solution {}", idx + 1)}</h3>
+                                <svg class="print-board"
+                                     width={ANSWER_WIDTH_PX.to_string()}
+                                     height={ANSWER_WIDTH_PX.to_string()}>
+                                    {sheet_svg(board, SheetContent::Solution, None, ANSWER_WIDTH_PX)}
+                                </svg>
+                            </div>
+                        }
+                    })
+                    .collect::<Html>();
+                html! { <div class="print-page">{answers}</div> }
+            })
+            .collect::<Html>()
+    } else {
+        html! {}
+    };
+
+    html! {
+        <>
+            <div class="panel no-print">
+                <h3>{"Print Puzzles"}</h3>
+                <p>
+                    <label for="print_per_page_inp">{"Puzzles per page:"}</label>
+                    <select id="print_per_page_inp" onchange={per_page_onchange}>
+                        {
+                            [1, 2, 4].iter().map(|n| html!{
+                                <option value={n.to_string()} selected={*n == *per_page}>
+                                    {n.to_string()}
+                                </option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                </p>
+                <p>
+                    <label for="print_answers_inp">{"Answer key:"}</label>
+                    <input id="print_answers_inp" type="checkbox"
+                           checked={*answer_key} onchange={answer_key_onchange}/>
+                </p>
+                <p>
+                    <label for="print_add_inp">{"Add puzzle (link or code):"}</label>
+                    <input id="print_add_inp" ref={add_ref}/>
+                    <button onclick={onadd} style="margin-left: 4px">{"Add"}</button>
+                </p>
+                <button onclick={onprint}>{"Print"}</button>
+            </div>
+            {puzzle_pages}
+            {answer_pages}
+        </>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_accept_plain_puzzle_code() {
+        assert_eq!(
+            puzzle_code_from_input(" BQAFAA==AAAAAAAA "),
+            Some("BQAFAA==AAAAAAAA".to_owned())
+        );
+    }
+
+    #[test]
+    fn should_extract_puzzle_code_from_link() {
+        assert_eq!(
+            puzzle_code_from_input("https://example.com/nonograms/solve/BQAFAA==AAAAAAAA/"),
+            Some("BQAFAA==AAAAAAAA".to_owned())
+        );
+    }
+
+    #[test]
+    fn should_reject_empty_input() {
+        assert_eq!(puzzle_code_from_input("  "), None);
+    }
+}
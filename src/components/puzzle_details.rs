@@ -0,0 +1,95 @@
+use crate::models::board::Board as BoardModel;
+use crate::models::metadata::Metadata;
+
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+// keep puzzle links short enough to share
+const MAX_TITLE_LEN: &str = "60";
+const MAX_AUTHOR_LEN: &str = "40";
+const MAX_DESCRIPTION_LEN: &str = "280";
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct PuzzleDetailsProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+}
+
+/// Current date as YYYY-MM-DD
+fn today() -> String {
+    let iso = String::from(js_sys::Date::new_0().to_iso_string());
+    iso.chars().take(10).collect()
+}
+
+/// Change the metadata in the puzzle code, dating the puzzle once it is first
+/// described
+fn update_metadata(puzzle: &UseStateHandle<AttrValue>, change: impl FnOnce(&mut Metadata)) {
+    let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+    let mut metadata = board.metadata_ref().clone();
+    let undated = metadata.created.is_empty();
+    change(&mut metadata);
+    if undated && metadata.created.is_empty() && !metadata.is_empty() {
+        metadata.created = today();
+    }
+    board.set_metadata(metadata);
+    puzzle.set(board.puzzle_code().into());
+}
+
+/// Title, author, description and date of the puzzle
+#[function_component(PuzzleDetails)]
+pub fn puzzle_details(props: &PuzzleDetailsProps) -> Html {
+    let metadata = BoardModel::from_serialized_solution(props.puzzle.as_ref())
+        .metadata_ref()
+        .clone();
+
+    let text_onchange = |field: fn(&mut Metadata) -> &mut String| {
+        let puzzle = props.puzzle.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            update_metadata(&puzzle, |metadata| {
+                *field(metadata) = input.value().trim().to_owned()
+            });
+        })
+    };
+    let description_onchange = {
+        let puzzle = props.puzzle.clone();
+        Callback::from(move |e: Event| {
+            let textarea: HtmlTextAreaElement = e.target_unchecked_into();
+            update_metadata(&puzzle, |metadata| {
+                metadata.description = textarea.value().trim().to_owned()
+            });
+        })
+    };
+    let hide_title_onchange = {
+        let puzzle = props.puzzle.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            update_metadata(&puzzle, |metadata| metadata.hide_title = input.checked());
+        })
+    };
+
+    html! {
+        <p>
+            <label for="title_inp">{"Title:"}</label>
+            <input id="title_inp" maxlength={MAX_TITLE_LEN} value={metadata.title.clone()}
+                   onchange={text_onchange(|metadata| &mut metadata.title)}/>
+            <br/>
+            <label for="hide_title_inp">{"Hide title until solved:"}</label>
+            <input id="hide_title_inp" type="checkbox" checked={metadata.hide_title}
+                   onchange={hide_title_onchange}/>
+            <br/>
+            <label for="author_inp">{"Author:"}</label>
+            <input id="author_inp" maxlength={MAX_AUTHOR_LEN} value={metadata.author.clone()}
+                   onchange={text_onchange(|metadata| &mut metadata.author)}/>
+            <br/>
+            <label for="created_inp">{"Created:"}</label>
+            <input id="created_inp" type="date" value={metadata.created.clone()}
+                   onchange={text_onchange(|metadata| &mut metadata.created)}/>
+            <br/>
+            <label for="description_inp">{"Description:"}</label>
+            <br/>
+            <textarea id="description_inp" rows="2" cols="30"
+                      maxlength={MAX_DESCRIPTION_LEN} value={metadata.description.clone()}
+                      onchange={description_onchange}/>
+        </p>
+    }
+}
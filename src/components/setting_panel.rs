@@ -1,74 +1,123 @@
+use crate::components::alternative_solutions::AlternativeSolutions;
+use crate::components::board::DrawingTool;
+use crate::components::board_size::BoardSize;
 use crate::components::copy_to_clipboard::CopyToClipboard;
-use crate::models::board::Board as BoardModel;
-use crate::routes::Route;
+use crate::components::image_import::ImageImport;
+use crate::components::picture_text::{ExportText, PastePicture};
+use crate::components::picture_transforms::PictureTransforms;
+use crate::components::puzzle_details::PuzzleDetails;
+use crate::components::text_to_picture::TextToPicture;
+use crate::components::underlay::{Underlay, UnderlayPanel};
+use crate::components::uniqueness_check::UniquenessCheck;
+use crate::models::board::FieldCell;
+use crate::models::grid::Grid;
+use crate::models::symmetry::Symmetry;
+use crate::routes::{self, Route};
 
-use wasm_bindgen::JsCast;
-use web_sys::EventTarget;
-use web_sys::HtmlAnchorElement;
 use web_sys::HtmlInputElement;
+use web_sys::HtmlSelectElement;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
+/// Lives offered for challenge links
+const LIVES_CHOICES: std::ops::RangeInclusive<u32> = 1..=5;
+const DEFAULT_LIVES: u32 = 3;
+
 #[derive(Properties, Clone, PartialEq)]
 pub struct SettingPanelProps {
     pub puzzle: UseStateHandle<AttrValue>,
     pub puzzle_width: UseStateHandle<usize>,
+    pub tool: UseStateHandle<DrawingTool>,
+    pub symmetry: UseStateHandle<Symmetry>,
+    pub underlay: UseStateHandle<Option<Underlay>>,
+    pub heatmap: UseStateHandle<bool>,
+    pub alternative: UseStateHandle<Option<Grid<FieldCell>>>,
 }
 
 #[function_component(SettingPanel)]
 pub fn setting_panel(props: &SettingPanelProps) -> Html {
-    let puzzle_state = props.puzzle.clone();
-    let width_state = props.puzzle_width.clone();
-    let width = *width_state;
-    let width_onchange = {
-        let puzzle = props.puzzle.clone();
+    let to_href = {
+        let navigator = use_navigator().expect("Failed to get navigator");
+        move |route: Route| routes::to_href(&navigator, &route)
+    };
+    let tool_buttons = DrawingTool::ALL
+        .iter()
+        .map(|&tool| {
+            let tool_state = props.tool.clone();
+            let onclick = Callback::from(move |_| tool_state.set(tool));
+            html! {
+                <button {onclick} disabled={tool == *props.tool}
+                        style="margin-right: 4px">{tool.label()}</button>
+            }
+        })
+        .collect::<Html>();
+    let symmetry_onchange = {
+        let symmetry = props.symmetry.clone();
         Callback::from(move |e: Event| {
-            let target: EventTarget =
-                e.target().expect("Error: No target on oninput event");
-            let setval = target.unchecked_into::<HtmlInputElement>().value();
-            let new_width = setval.parse().expect("Could not parse width");
-            width_state.set(new_width);
-            log::info!("Increasing puzzle width to {new_width}");
-            let mut grid = BoardModel::from_serialized_solution(puzzle.as_ref());
-            grid.resize(new_width);
-            puzzle_state.set(grid.solution_ref().serialize_base64().into())
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let idx = select.value().parse().unwrap_or(0);
+            symmetry.set(Symmetry::ALL[idx]);
         })
     };
-    // to get an 'absolute' URI from a Route
-    let to_href = {
-        let navigator = use_navigator().expect("Failed to get navigator");
-        let anchor = web_sys::window()
-            .expect("Could not get window")
-            .document()
-            .expect("Could not get document")
-            .create_element("a")
-            .expect("Could not create anchor")
-            .unchecked_into::<HtmlAnchorElement>();
-        move |route: Route| {
-            let url = match navigator.basename() {
-                Some(base) => format!("{}{}", base, route.to_path()),
-                None => route.to_path(),
-            };
-            anchor.set_href(&url);
-            anchor.href()
-        }
+    let lives = use_state(|| DEFAULT_LIVES);
+    let lives_onchange = {
+        let lives = lives.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            lives.set(select.value().parse().unwrap_or(DEFAULT_LIVES));
+        })
+    };
+    let heatmap_onchange = {
+        let heatmap = props.heatmap.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            heatmap.set(input.checked());
+        })
     };
     html! {
         <div style={"display:flex"}>
         <div class="panel">
             <h3>{"Set a Nonogram"}</h3>
-            <label for="puzzle_width_input">{"Width:"}</label>
-            <select id={"puzzle_width_input"} onchange={width_onchange}>
-                {
-                    (5..=25).map(|w| html!{
-                        <option
-                            value={w.to_string()}
-                            selected={w == width}>
-                           {w.to_string()}
-                        </option>
-                    }).collect::<Html>()
-                }
-            </select>
+            <BoardSize puzzle={props.puzzle.clone()}
+                       puzzle_width={props.puzzle_width.clone()}/>
+            <PuzzleDetails puzzle={props.puzzle.clone()}/>
+            <p>
+                {"Draw: "}{tool_buttons}
+                <br/>
+                {"Left button sets cells, right button clears them"}
+            </p>
+            <p>
+                <label for="symmetry_inp">{"Mirror:"}</label>
+                <select id="symmetry_inp" onchange={symmetry_onchange}>
+                    {
+                        Symmetry::ALL.iter().enumerate().map(|(idx, symmetry)| html!{
+                            <option value={idx.to_string()}
+                                    selected={*symmetry == *props.symmetry}>
+                                {symmetry.label()}
+                            </option>
+                        }).collect::<Html>()
+                    }
+                </select>
+            </p>
+            <PictureTransforms puzzle={props.puzzle.clone()}/>
+            <p>
+                <label for="heatmap_inp">{"Show solvability:"}</label>
+                <input id="heatmap_inp" type="checkbox"
+                       checked={*props.heatmap} onchange={heatmap_onchange}/>
+                <UniquenessCheck puzzle={props.puzzle.clone()}/>
+                <AlternativeSolutions puzzle={props.puzzle.clone()}
+                                      alternative={props.alternative.clone()}/>
+            </p>
+            <p>
+                <PastePicture puzzle={props.puzzle.clone()}
+                              puzzle_width={props.puzzle_width.clone()}/>
+                <ExportText puzzle={(*props.puzzle).clone()}/>
+                <ImageImport puzzle={props.puzzle.clone()}
+                             puzzle_width={props.puzzle_width.clone()}/>
+                <TextToPicture puzzle={props.puzzle.clone()}
+                               puzzle_width={props.puzzle_width.clone()}/>
+                <UnderlayPanel underlay={props.underlay.clone()}/>
+            </p>
             <p>
                 <label for={"solvelink_inp"}>{"Link (Solve):"}</label>
                 <CopyToClipboard
@@ -76,6 +125,27 @@ pub fn setting_panel(props: &SettingPanelProps) -> Html {
                     input_id={"solvelink_inp"}
                 />
             </p>
+            <p>
+                <label for={"challengelink_inp"}>{"Link (Challenge):"}</label>
+                <CopyToClipboard
+                    value={to_href(Route::SolveChallenge {
+                        puzzle: props.puzzle.to_string(),
+                        lives: *lives,
+                    })}
+                    input_id={"challengelink_inp"}
+                />
+                <br/>
+                <label for="lives_inp">{"Lives:"}</label>
+                <select id="lives_inp" onchange={lives_onchange}>
+                    {
+                        LIVES_CHOICES.map(|n| html!{
+                            <option value={n.to_string()} selected={n == *lives}>
+                                {n}
+                            </option>
+                        }).collect::<Html>()
+                    }
+                </select>
+            </p>
             <p>
                 <label for={"setlink_inp"}>{"Link (Continue Setting):"}</label>
                 <CopyToClipboard
@@ -83,6 +153,17 @@ pub fn setting_panel(props: &SettingPanelProps) -> Html {
                     input_id={"setlink_inp"}
                 />
             </p>
+            <p>
+                <Link<Route> to={Route::Print{puzzles: props.puzzle.to_string()}}>
+                    {"Printable sheet"}
+                </Link<Route>>
+            </p>
+            <p>
+                <Link<Route> to={Route::Clues}>{"Enter a puzzle by its clues"}</Link<Route>>
+            </p>
+            <p>
+                <Link<Route> to={Route::Library}>{"My puzzles"}</Link<Route>>
+            </p>
         </div>
         </div>
     }
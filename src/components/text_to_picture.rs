@@ -0,0 +1,102 @@
+use crate::components::board::NonogramPreview;
+use crate::models::board::{Board as BoardModel, FieldCell};
+use crate::models::font::render_text;
+use crate::models::grid::Grid;
+
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+const MIN_WIDTH: usize = 5;
+const MAX_WIDTH: usize = 25;
+const PREVIEW_PX: u32 = 150;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct TextToPictureProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+    pub puzzle_width: UseStateHandle<usize>,
+}
+
+/// Smallest square board holding the text, with a blank border if there is room
+fn lettering(text: &str) -> Result<Grid<FieldCell>, String> {
+    let picture = render_text(text);
+    let size = std::cmp::max(picture.width(), picture.height());
+    if size == 0 {
+        return Err("Enter some text".to_owned());
+    }
+    if size > MAX_WIDTH {
+        return Err(format!(
+            "Text needs {}x{} cells, at most {MAX_WIDTH}x{MAX_WIDTH} is supported",
+            picture.width(),
+            picture.height()
+        ));
+    }
+    let size = (size + 2).clamp(MIN_WIDTH, MAX_WIDTH);
+    Ok(picture.centered(size, size))
+}
+
+/// Dialog to draw text into the solution using the built-in pixel font
+#[function_component(TextToPicture)]
+pub fn text_to_picture(props: &TextToPictureProps) -> Html {
+    let open = use_state(|| false);
+    let text = use_state(String::new);
+
+    let toggle = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(!*open))
+    };
+    let oninput = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            text.set(input.value());
+        })
+    };
+    let picture = lettering(&text);
+    let onapply = {
+        let open = open.clone();
+        let picture = picture.clone().ok();
+        let puzzle = props.puzzle.clone();
+        let puzzle_width = props.puzzle_width.clone();
+        Callback::from(move |_| {
+            if let Some(picture) = &picture {
+                log::info!("Drawing text into {0}x{0} puzzle", picture.width());
+                puzzle_width.set(picture.width());
+                let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+                board.set_picture(picture);
+                puzzle.set(board.puzzle_code().into());
+            }
+            open.set(false);
+        })
+    };
+
+    html! {
+        <>
+            <button onclick={toggle.clone()} style="margin-left: 4px">{"Text to picture"}</button>
+            if *open {
+                <div class="panel">
+                    <p>
+                        <textarea rows="3" cols="20" placeholder={"HI\nTEAM"}
+                                  value={(*text).clone()} {oninput}/>
+                    </p>
+                    {
+                        match &picture {
+                            Ok(picture) => html! {
+                                <p>
+                                    <svg width={PREVIEW_PX.to_string()}
+                                         height={PREVIEW_PX.to_string()}>
+                                        <NonogramPreview field={picture.clone()}
+                                                         width_px={PREVIEW_PX}
+                                                         margin_px={0}/>
+                                    </svg>
+                                </p>
+                            },
+                            Err(error) => html! { <p>{error}</p> },
+                        }
+                    }
+                    <button onclick={onapply} disabled={picture.is_err()}>{"Use picture"}</button>
+                    <button onclick={toggle} style="margin-left: 4px">{"Cancel"}</button>
+                </div>
+            }
+        </>
+    }
+}
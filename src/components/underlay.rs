@@ -0,0 +1,172 @@
+use crate::components::image_file::load_image;
+use crate::storage;
+
+use web_sys::{File, HtmlInputElement};
+use yew::prelude::*;
+
+// keeps the stored image well within the local storage quota
+const MAX_IMAGE_PX: u32 = 800;
+const IMAGE_KEY: &str = "underlay-image";
+const SETTINGS_KEY: &str = "underlay-settings";
+
+/// Picture shown behind the board in Set mode to trace it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Underlay {
+    /// Data URL of the image
+    pub image: AttrValue,
+    /// In percent
+    pub opacity: u8,
+    /// Horizontal and vertical shift in percent of the field width
+    pub offset: (i32, i32),
+}
+
+impl Underlay {
+    fn new(image: AttrValue) -> Self {
+        Self {
+            image,
+            opacity: 50,
+            offset: (0, 0),
+        }
+    }
+
+    /// Underlay kept in browser storage, if any
+    pub fn load() -> Option<Self> {
+        let mut underlay = Self::new(storage::load(IMAGE_KEY)?.into());
+        let settings = storage::load(SETTINGS_KEY).unwrap_or_default();
+        let values = settings
+            .split(',')
+            .filter_map(|value| value.parse::<i32>().ok())
+            .collect::<Vec<_>>();
+        if let [opacity, offset_x, offset_y] = values[..] {
+            underlay.opacity = opacity.clamp(0, 100) as u8;
+            underlay.offset = (offset_x, offset_y);
+        }
+        Some(underlay)
+    }
+
+    fn store(&self) {
+        storage::store(
+            SETTINGS_KEY,
+            &format!("{},{},{}", self.opacity, self.offset.0, self.offset.1),
+        );
+    }
+
+    /// Image scaled into the field area of the board
+    pub fn svg(&self, field_x_px: usize, field_width_px: usize) -> Html {
+        let shift =
+            |percent: i32| (field_x_px as i32 + field_width_px as i32 * percent / 100).to_string();
+        html! {
+            <image href={self.image.clone()}
+                   x={shift(self.offset.0)} y={shift(self.offset.1)}
+                   width={field_width_px.to_string()} height={field_width_px.to_string()}
+                   preserveAspectRatio="xMidYMid meet"
+                   opacity={format!("{:.2}", self.opacity as f64 / 100.0)}
+                   style="pointer-events: none"/>
+        }
+    }
+}
+
+/// Downscale the image file and emit it as data URL once loaded
+fn load_data_url(file: &File, on_loaded: Callback<String>) {
+    load_image(file, MAX_IMAGE_PX, move |context| {
+        let data_url = context
+            .canvas()
+            .and_then(|canvas| canvas.to_data_url_with_type("image/jpeg").ok());
+        match data_url {
+            Some(data_url) => on_loaded.emit(data_url),
+            None => log::warn!("Could not read uploaded reference image"),
+        }
+    });
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct UnderlayPanelProps {
+    pub underlay: UseStateHandle<Option<Underlay>>,
+}
+
+/// Dialog to choose and adjust the reference image behind the board
+#[function_component(UnderlayPanel)]
+pub fn underlay_panel(props: &UnderlayPanelProps) -> Html {
+    let open = use_state(|| false);
+
+    let toggle = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(!*open))
+    };
+    let file_onchange = {
+        let underlay = props.underlay.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let underlay = underlay.clone();
+                load_data_url(
+                    &file,
+                    Callback::from(move |data_url: String| {
+                        if !storage::store(IMAGE_KEY, &data_url) {
+                            log::warn!("Reference image is only kept until the page is left");
+                        }
+                        let new_underlay = Underlay::new(data_url.into());
+                        new_underlay.store();
+                        underlay.set(Some(new_underlay));
+                    }),
+                );
+            }
+        })
+    };
+    let adjust = |change: fn(&mut Underlay, i32)| {
+        let underlay = props.underlay.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let (Some(mut adjusted), Ok(value)) = ((*underlay).clone(), input.value().parse()) {
+                change(&mut adjusted, value);
+                adjusted.store();
+                underlay.set(Some(adjusted));
+            }
+        })
+    };
+    let onremove = {
+        let underlay = props.underlay.clone();
+        Callback::from(move |_| {
+            storage::remove(IMAGE_KEY);
+            storage::remove(SETTINGS_KEY);
+            underlay.set(None);
+        })
+    };
+
+    html! {
+        <>
+            <button onclick={toggle} style="margin-left: 4px">{"Reference image"}</button>
+            if *open {
+                <div class="panel">
+                    <p>
+                        <label for="underlay_file_inp">{"Image to trace:"}</label>
+                        <input id="underlay_file_inp" type="file"
+                               accept="image/png, image/jpeg" onchange={file_onchange}/>
+                    </p>
+                    if let Some(underlay) = &*props.underlay {
+                        <p>
+                            <label for="underlay_opacity_inp">{"Opacity:"}</label>
+                            <input id="underlay_opacity_inp" type="range" min="0" max="100"
+                                   value={underlay.opacity.to_string()}
+                                   oninput={adjust(|u, value| u.opacity = value as u8)}/>
+                        </p>
+                        <p>
+                            <label for="underlay_x_inp">{"Shift right:"}</label>
+                            <input id="underlay_x_inp" type="range" min="-50" max="50"
+                                   value={underlay.offset.0.to_string()}
+                                   oninput={adjust(|u, value| u.offset.0 = value)}/>
+                        </p>
+                        <p>
+                            <label for="underlay_y_inp">{"Shift down:"}</label>
+                            <input id="underlay_y_inp" type="range" min="-50" max="50"
+                                   value={underlay.offset.1.to_string()}
+                                   oninput={adjust(|u, value| u.offset.1 = value)}/>
+                        </p>
+                        <button onclick={onremove}>{"Remove image"}</button>
+                    }
+                    <p>{"The image is kept in this browser only, it is not part of the puzzle"}</p>
+                </div>
+            }
+        </>
+    }
+}
@@ -0,0 +1,200 @@
+use crate::models::board::{Board as BoardModel, FieldCell};
+use crate::models::clues::Clues;
+use crate::models::grid::Grid;
+use crate::models::solver::solutions_from;
+use crate::models::uniqueness::{suggest_flips, suggest_givens, GaveUp};
+
+use yew::prelude::*;
+
+const MAX_FLIPS: usize = 6;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct UniquenessCheckProps {
+    pub puzzle: UseStateHandle<AttrValue>,
+}
+
+/// A suggestion, None until the author asks for it
+type Suggestion<T> = Option<Result<T, GaveUp>>;
+
+#[derive(Clone, PartialEq)]
+struct Analysis {
+    /// Puzzle the suggestions were made for
+    puzzle: AttrValue,
+    /// Whether the puzzle, givens included, has a single solution, None if the
+    /// search gave up
+    unique: Option<bool>,
+    flips: Suggestion<Option<Vec<(usize, usize)>>>,
+    /// Suggested givens including the puzzle's own
+    givens: Suggestion<Grid<FieldCell>>,
+}
+
+fn cell_list(cells: impl Iterator<Item = String>) -> Html {
+    html! { <ul>{ cells.map(|cell| html! { <li>{cell}</li> }).collect::<Html>() }</ul> }
+}
+
+/// Cells revealed in `givens` but not in `existing` as (row, col, filled)
+fn given_cells<'a>(
+    givens: &'a Grid<FieldCell>,
+    existing: &'a Grid<FieldCell>,
+) -> impl Iterator<Item = (usize, usize, bool)> + 'a {
+    (0..givens.height())
+        .flat_map(|row| (0..givens.width()).map(move |col| (row, col)))
+        .filter(|&(row, col)| {
+            givens[row][col] != FieldCell::Empty && existing[row][col] == FieldCell::Empty
+        })
+        .map(|(row, col)| (row, col, givens[row][col] == FieldCell::Filled))
+}
+
+/// Check whether the solution is unique and suggest changes if it is not. Each
+/// suggestion is searched for only when asked for, since together they would
+/// keep the page busy for too long
+#[function_component(UniquenessCheck)]
+pub fn uniqueness_check(props: &UniquenessCheckProps) -> Html {
+    let analysis = use_state(|| Option::<Analysis>::None);
+
+    let oncheck = {
+        let (analysis, puzzle) = (analysis.clone(), props.puzzle.clone());
+        Callback::from(move |_| {
+            let board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            let picture = board.solution_ref();
+            let clues = Clues::from_solution(picture);
+            let unique = solutions_from(&clues, board.givens_ref(), 2).unique();
+            analysis.set(Some(Analysis {
+                puzzle: (*puzzle).clone(),
+                unique,
+                flips: None,
+                givens: None,
+            }));
+        })
+    };
+    let onsuggest_flips = {
+        let (analysis, puzzle) = (analysis.clone(), props.puzzle.clone());
+        Callback::from(move |_| {
+            let Some(current) = analysis.as_ref() else {
+                return;
+            };
+            let board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            let flips = suggest_flips(board.solution_ref(), board.givens_ref(), MAX_FLIPS);
+            analysis.set(Some(Analysis {
+                flips: Some(flips),
+                ..current.clone()
+            }));
+        })
+    };
+    let onsuggest_givens = {
+        let (analysis, puzzle) = (analysis.clone(), props.puzzle.clone());
+        Callback::from(move |_| {
+            let Some(current) = analysis.as_ref() else {
+                return;
+            };
+            let board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            let givens = suggest_givens(board.solution_ref(), board.givens_ref());
+            analysis.set(Some(Analysis {
+                givens: Some(givens),
+                ..current.clone()
+            }));
+        })
+    };
+    let onapply_flips = {
+        let (analysis, puzzle) = (analysis.clone(), props.puzzle.clone());
+        Callback::from(move |_| {
+            let Some(Some(Ok(Some(flips)))) = analysis.as_ref().map(|a| a.flips.clone()) else {
+                return;
+            };
+            let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            for (row, col) in flips {
+                let filled = board.solution(row, col) == FieldCell::Filled;
+                board.set(row, col, !filled);
+            }
+            log::info!("Applied suggested flips to make the puzzle unique");
+            puzzle.set(board.puzzle_code().into());
+            analysis.set(None);
+        })
+    };
+    let onapply_givens = {
+        let (analysis, puzzle) = (analysis.clone(), props.puzzle.clone());
+        Callback::from(move |_| {
+            let Some(Some(Ok(givens))) = analysis.as_ref().map(|a| a.givens.as_ref()) else {
+                return;
+            };
+            let mut board = BoardModel::from_serialized_solution(puzzle.as_ref());
+            board.set_givens(givens);
+            log::info!("Revealed suggested givens to make the puzzle unique");
+            puzzle.set(board.puzzle_code().into());
+            analysis.set(None);
+        })
+    };
+
+    let result = match &*analysis {
+        Some(analysis) if analysis.puzzle == *props.puzzle => {
+            if analysis.unique == Some(true) {
+                html! { <p>{"The solution is unique."}</p> }
+            } else if analysis.unique.is_none() {
+                html! {
+                    <p>
+                        {"The search gave up before telling whether the solution is unique."}
+                    </p>
+                }
+            } else {
+                let flips = match &analysis.flips {
+                    None => html! {
+                        <button onclick={onsuggest_flips}>{"Suggest cells to flip"}</button>
+                    },
+                    Some(Ok(Some(flips))) => html! {
+                        <>
+                            <p>{"Flipping these cells makes the solution unique:"}</p>
+                            {cell_list(flips.iter().map(|(row, col)| {
+                                format!("Row {}, column {}", row + 1, col + 1)
+                            }))}
+                            <button onclick={onapply_flips}>{"Flip cells"}</button>
+                        </>
+                    },
+                    Some(Ok(None)) => html! {
+                        <p>{format!("No way found to make it unique with {MAX_FLIPS} flips.")}</p>
+                    },
+                    Some(Err(GaveUp)) => html! {
+                        <p>{"The search gave up before finding cells to flip."}</p>
+                    },
+                };
+                let givens = match &analysis.givens {
+                    None => html! {
+                        <button onclick={onsuggest_givens}>{"Suggest cells to reveal"}</button>
+                    },
+                    Some(Ok(givens)) => {
+                        let existing = BoardModel::from_serialized_solution(props.puzzle.as_ref());
+                        html! {
+                            <>
+                                <p>{"Revealing these cells at the start makes the solution unique:"}</p>
+                                {cell_list(given_cells(givens, existing.givens_ref()).map(
+                                    |(row, col, filled)| {
+                                        let state = if filled { "filled" } else { "empty" };
+                                        format!("Row {}, column {} ({state})", row + 1, col + 1)
+                                    }
+                                ))}
+                                <button onclick={onapply_givens}>{"Reveal cells"}</button>
+                            </>
+                        }
+                    }
+                    Some(Err(GaveUp)) => html! {
+                        <p>{"The search gave up before finding cells to reveal."}</p>
+                    },
+                };
+                html! {
+                    <>
+                        <p>{"The clues have more than one solution."}</p>
+                        {flips}
+                        {givens}
+                    </>
+                }
+            }
+        }
+        _ => html!(),
+    };
+
+    html! {
+        <>
+            <button onclick={oncheck} style="margin-left: 4px">{"Check uniqueness"}</button>
+            {result}
+        </>
+    }
+}
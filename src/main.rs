@@ -1,9 +1,17 @@
 mod components;
 mod models;
 mod routes;
+mod storage;
 
-use crate::components::board::{Board as BoardComponent, BoardMode};
+use crate::components::board::{Board as BoardComponent, BoardMode, DrawingTool};
+use crate::components::clue_entry::ClueEntry;
+use crate::components::library::PuzzleLibrary;
+use crate::components::print_view::PrintView;
 use crate::components::setting_panel::SettingPanel;
+use crate::components::underlay::Underlay;
+use crate::models::board::FieldCell;
+use crate::models::grid::Grid;
+use crate::models::symmetry::Symmetry;
 use crate::routes::Route;
 
 use yew::prelude::*;
@@ -13,25 +21,47 @@ use yew_router::prelude::*;
 struct MainProps {
     pub mode: BoardMode,
     pub puzzle: String,
+    pub lives: Option<u32>,
+    pub draft: Option<u32>,
 }
 
 #[function_component(MainComp)]
 fn main_component(props: &MainProps) -> Html {
     let puzzle_width = use_state(|| 10);
     let puzzle = use_state(|| AttrValue::from(props.puzzle.clone()));
+    let tool = use_state(|| DrawingTool::Rectangle);
+    let symmetry = use_state(|| Symmetry::None);
+    let underlay = use_state(Underlay::load);
+    let heatmap = use_state(|| false);
+    let alternative = use_state(|| Option::<Grid<FieldCell>>::None);
 
     html! {
         <div class={"content-box"}>
             if props.mode == BoardMode::Set {
-                <SettingPanel puzzle={puzzle.clone()} puzzle_width={puzzle_width}/>
+                <SettingPanel puzzle={puzzle.clone()} puzzle_width={puzzle_width}
+                              tool={tool.clone()} symmetry={symmetry.clone()}
+                              underlay={underlay.clone()} heatmap={heatmap.clone()}
+                              alternative={alternative.clone()}/>
+            } else if props.mode == BoardMode::Clues {
+                <ClueEntry puzzle={puzzle.clone()} puzzle_width={puzzle_width}/>
+            }
+            <BoardComponent mode={props.mode} tool={*tool} symmetry={*symmetry}
+                            underlay={(*underlay).clone()} heatmap={*heatmap}
+                            alternative={(*alternative).clone()}
+                            lives={props.lives} draft={props.draft} puzzle={puzzle}/>
+            if props.mode == BoardMode::Solve {
+                <p style="text-align: center">
+                    <Link<Route> to={Route::Library}>{"My puzzles"}</Link<Route>>
+                </p>
             }
-            <BoardComponent mode={props.mode} puzzle={puzzle}/>
         </div>
     }
 }
 
 fn switch(route: Route) -> Html {
     const STARTER_PUZZLE: &str = "CgAKAA==ABAAQAAAAQAEAFRVQRQQVAFBFBBUVQEAAA";
+    let mut lives = None;
+    let mut draft = None;
     let (mode, puzzle) = match route {
         Route::Home => {
             return html! {
@@ -39,13 +69,40 @@ fn switch(route: Route) -> Html {
             }
         }
         Route::Solve { puzzle } => (BoardMode::Solve, puzzle.clone()),
+        Route::SolveChallenge { puzzle, lives: n } => {
+            lives = Some(n);
+            (BoardMode::Solve, puzzle.clone())
+        }
         Route::Set { puzzle } => (BoardMode::Set, puzzle.clone()),
+        Route::SetDraft { puzzle, draft: id } => {
+            draft = Some(id);
+            (BoardMode::Set, puzzle.clone())
+        }
         Route::SetNew => (BoardMode::Set, "".to_owned()),
+        Route::Clues => (
+            BoardMode::Clues,
+            Grid::<FieldCell>::new(10, 10).serialize_base64(),
+        ),
+        Route::Print { puzzles } => {
+            return html! {
+                <PrintView puzzles={puzzles}/>
+            }
+        }
+        Route::Library => {
+            return html! {
+                <>
+                    <h1>{"Nonogram Game"}</h1>
+                    <div class={"content-box"}>
+                        <PuzzleLibrary/>
+                    </div>
+                </>
+            }
+        }
     };
     html! {
         <>
             <h1>{"Nonogram Game"}</h1>
-            <MainComp mode={mode} puzzle={puzzle}/>
+            <MainComp mode={mode} puzzle={puzzle} lives={lives} draft={draft}/>
         </>
     }
 }
@@ -0,0 +1,241 @@
+use super::board::FieldCell;
+use super::grid::Grid;
+
+/// How to deal with lines shorter than the longest one when importing text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RaggedLines {
+    /// Pad short lines with empty cells
+    Pad,
+    /// Refuse input where lines differ in length
+    Reject,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextFormat {
+    pub filled: char,
+    pub empty: char,
+    /// Drop blank lines and columns around the picture
+    pub trim: bool,
+    pub ragged: RaggedLines,
+}
+
+impl Default for TextFormat {
+    fn default() -> Self {
+        Self {
+            filled: '#',
+            empty: '.',
+            trim: true,
+            ragged: RaggedLines::Pad,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TextImportError {
+    NoContent,
+    /// (line, length, expected length), lines counted from 0
+    RaggedLine(usize, usize, usize),
+    /// (line, column, character), counted from 0
+    UnexpectedChar(usize, usize, char),
+}
+
+impl std::fmt::Display for TextImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextImportError::NoContent => write!(f, "No picture found"),
+            TextImportError::RaggedLine(line, len, expected) => write!(
+                f,
+                "Line {} has {len} characters, expected {expected}",
+                line + 1
+            ),
+            TextImportError::UnexpectedChar(line, col, c) => write!(
+                f,
+                "Unexpected character '{c}' in line {}, column {}",
+                line + 1,
+                col + 1
+            ),
+        }
+    }
+}
+
+impl Grid<FieldCell> {
+    /// Render filled cells with `format.filled`, everything else with `format.empty`,
+    /// one line per row
+    pub fn to_text(&self, format: &TextFormat) -> String {
+        let mut res = String::with_capacity((self.width() + 1) * self.height());
+        for row in 0..self.height() {
+            res.extend(self[row].iter().map(|cell| match cell {
+                FieldCell::Filled => format.filled,
+                _ => format.empty,
+            }));
+            res.push('\n');
+        }
+        res
+    }
+
+    /// Parse a picture drawn with `format.filled` and `format.empty`, spaces
+    /// also count as empty
+    pub fn from_text(src: &str, format: &TextFormat) -> Result<Self, TextImportError> {
+        let is_blank = |c: char| c == format.empty || c == ' ';
+        let mut lines = src
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if format.trim {
+            let blank_line = |line: &Vec<char>| line.iter().all(|&c| is_blank(c));
+            while lines.last().is_some_and(blank_line) {
+                lines.pop();
+            }
+            let leading = lines.iter().take_while(|line| blank_line(line)).count();
+            lines.drain(..leading);
+            let indent = lines
+                .iter()
+                .filter(|line| !blank_line(line))
+                .map(|line| line.iter().take_while(|&&c| is_blank(c)).count())
+                .min()
+                .unwrap_or(0);
+            let content_width = lines
+                .iter()
+                .filter_map(|line| line.iter().rposition(|&c| !is_blank(c)))
+                .max()
+                .map_or(0, |pos| pos + 1 - indent);
+            for line in lines.iter_mut() {
+                line.drain(..indent.min(line.len()));
+                line.truncate(content_width);
+            }
+        }
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        if width == 0 {
+            return Err(TextImportError::NoContent);
+        }
+        let mut grid = Grid::new(width, lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            if format.ragged == RaggedLines::Reject && line.len() != width {
+                return Err(TextImportError::RaggedLine(row, line.len(), width));
+            }
+            for (col, &c) in line.iter().enumerate() {
+                grid[row][col] = if c == format.filled {
+                    FieldCell::Filled
+                } else if is_blank(c) {
+                    FieldCell::Empty
+                } else {
+                    return Err(TextImportError::UnexpectedChar(row, col, c));
+                };
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FieldCell::{Empty as E, Filled as F};
+
+    #[test]
+    fn should_export_filled_and_empty_cells() {
+        let grid = Grid::from_flat(3, &[F, E, F, E, F, E]);
+        assert_eq!(grid.to_text(&TextFormat::default()), "#.#\n.#.\n");
+    }
+
+    #[test]
+    fn should_export_marked_cells_as_empty() {
+        let grid = Grid::from_flat(2, &[F, FieldCell::Marked]);
+        assert_eq!(grid.to_text(&TextFormat::default()), "#.\n");
+    }
+
+    #[test]
+    fn should_export_with_custom_characters() {
+        let format = TextFormat {
+            filled: 'X',
+            empty: ' ',
+            ..Default::default()
+        };
+        let grid = Grid::from_flat(2, &[F, E, E, F]);
+        assert_eq!(grid.to_text(&format), "X \n X\n");
+    }
+
+    #[test]
+    fn should_reproduce_grid_after_export_and_import() {
+        let grid = Grid::from_flat(3, &[E, F, E, F, F, F, E, F, E]);
+        let format = TextFormat {
+            trim: false,
+            ..Default::default()
+        };
+        let text = grid.to_text(&format);
+        assert_eq!(Grid::from_text(&text, &format), Ok(grid));
+    }
+
+    #[test]
+    fn should_reject_empty_input() {
+        let format = TextFormat::default();
+        assert_eq!(
+            Grid::from_text("", &format),
+            Err(TextImportError::NoContent)
+        );
+        assert_eq!(
+            Grid::from_text("\n\n  \n", &format),
+            Err(TextImportError::NoContent)
+        );
+        assert_eq!(
+            Grid::from_text("...\n...", &format),
+            Err(TextImportError::NoContent)
+        );
+    }
+
+    #[test]
+    fn should_trim_blank_lines_and_indentation() {
+        let src = "\n  ......\n  ..#.\n  .###\n\n";
+        let grid = Grid::from_text(src, &TextFormat::default()).unwrap();
+        assert_eq!(grid, Grid::from_flat(3, &[E, F, E, F, F, F]));
+    }
+
+    #[test]
+    fn should_keep_inner_empty_lines() {
+        let grid = Grid::from_text("#\n\n#", &TextFormat::default()).unwrap();
+        assert_eq!(grid, Grid::from_flat(1, &[F, E, F]));
+    }
+
+    #[test]
+    fn should_keep_blank_border_without_trimming() {
+        let format = TextFormat {
+            trim: false,
+            ..Default::default()
+        };
+        let grid = Grid::from_text("...\n.#.\n...", &format).unwrap();
+        assert_eq!(grid, Grid::from_flat(3, &[E, E, E, E, F, E, E, E, E]));
+    }
+
+    #[test]
+    fn should_pad_ragged_lines() {
+        let grid = Grid::from_text("#\n###\n##", &TextFormat::default()).unwrap();
+        assert_eq!(grid, Grid::from_flat(3, &[F, E, E, F, F, F, F, F, E]));
+    }
+
+    #[test]
+    fn should_reject_ragged_lines_if_requested() {
+        let format = TextFormat {
+            ragged: RaggedLines::Reject,
+            ..Default::default()
+        };
+        assert_eq!(
+            Grid::from_text("###\n#\n###", &format),
+            Err(TextImportError::RaggedLine(1, 1, 3))
+        );
+        assert!(Grid::from_text("###\n#..\n###", &format).is_ok());
+    }
+
+    #[test]
+    fn should_accept_windows_line_endings() {
+        let grid = Grid::from_text("#.\r\n.#\r\n", &TextFormat::default()).unwrap();
+        assert_eq!(grid, Grid::from_flat(2, &[F, E, E, F]));
+    }
+
+    #[test]
+    fn should_report_unexpected_characters() {
+        assert_eq!(
+            Grid::from_text("#.\n.x", &TextFormat::default()),
+            Err(TextImportError::UnexpectedChar(1, 1, 'x'))
+        );
+    }
+}
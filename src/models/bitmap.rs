@@ -0,0 +1,153 @@
+use super::board::FieldCell;
+use super::grid::Grid;
+
+/// RGBA image data as obtained from a canvas, 4 bytes per pixel, row by row
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    rgba: Vec<u8>,
+}
+
+impl Bitmap {
+    pub fn new(width: usize, height: usize, rgba: Vec<u8>) -> Self {
+        assert_eq!(rgba.len(), width * height * 4, "RGBA data length mismatch");
+        Self {
+            width,
+            height,
+            rgba,
+        }
+    }
+
+    /// Brightness of a pixel from 0 (black) to 255 (white), transparency is
+    /// treated as white background
+    fn luminance(&self, x: usize, y: usize) -> f32 {
+        let idx = (y * self.width + x) * 4;
+        let [r, g, b, a] = [0, 1, 2, 3].map(|i| self.rgba[idx + i] as f32);
+        let lum = 0.299 * r + 0.587 * g + 0.114 * b;
+        let alpha = a / 255.0;
+        lum * alpha + 255.0 * (1.0 - alpha)
+    }
+
+    /// Average luminance over the area of each target cell, row by row
+    pub fn downsample(&self, target_width: usize, target_height: usize) -> Vec<f32> {
+        let mut res = Vec::with_capacity(target_width * target_height);
+        for ty in 0..target_height {
+            let y0 = ty * self.height / target_height;
+            let y1 = ((ty + 1) * self.height / target_height).max(y0 + 1);
+            for tx in 0..target_width {
+                let x0 = tx * self.width / target_width;
+                let x1 = ((tx + 1) * self.width / target_width).max(x0 + 1);
+                let (mut sum, mut n) = (0.0, 0);
+                for y in y0..y1.min(self.height) {
+                    for x in x0..x1.min(self.width) {
+                        sum += self.luminance(x, y);
+                        n += 1;
+                    }
+                }
+                res.push(if n > 0 { sum / n as f32 } else { 255.0 });
+            }
+        }
+        res
+    }
+
+    /// Convert to a picture where cells darker than `threshold` are filled,
+    /// optionally diffusing the rounding error (Floyd-Steinberg)
+    pub fn to_grid(
+        &self,
+        target_width: usize,
+        target_height: usize,
+        threshold: u8,
+        dither: bool,
+    ) -> Grid<FieldCell> {
+        let mut values = self.downsample(target_width, target_height);
+        let mut grid = Grid::new(target_width, target_height);
+        for y in 0..target_height {
+            for x in 0..target_width {
+                let value = values[y * target_width + x];
+                let filled = value < threshold as f32;
+                if filled {
+                    grid[y][x] = FieldCell::Filled;
+                }
+                if !dither {
+                    continue;
+                }
+                let error = value - if filled { 0.0 } else { 255.0 };
+                let mut diffuse = |dx: isize, dy: usize, weight: f32| {
+                    let nx = x as isize + dx;
+                    if nx < 0 || nx as usize >= target_width || y + dy >= target_height {
+                        return;
+                    }
+                    values[(y + dy) * target_width + nx as usize] += error * weight;
+                };
+                diffuse(1, 0, 7.0 / 16.0);
+                diffuse(-1, 1, 3.0 / 16.0);
+                diffuse(0, 1, 5.0 / 16.0);
+                diffuse(1, 1, 1.0 / 16.0);
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grey_bitmap(width: usize, height: usize, values: &[u8]) -> Bitmap {
+        let rgba = values.iter().flat_map(|&v| [v, v, v, 255]).collect();
+        Bitmap::new(width, height, rgba)
+    }
+
+    #[test]
+    fn should_treat_transparent_pixels_as_white() {
+        let bitmap = Bitmap::new(1, 1, vec![0, 0, 0, 0]);
+        assert_eq!(bitmap.downsample(1, 1), vec![255.0]);
+    }
+
+    #[test]
+    fn should_average_over_cell_area() {
+        let bitmap = grey_bitmap(4, 2, &[0, 0, 255, 255, 0, 0, 255, 255]);
+        assert_eq!(bitmap.downsample(2, 1), vec![0.0, 255.0]);
+        assert_eq!(bitmap.downsample(1, 1), vec![127.5]);
+    }
+
+    #[test]
+    fn should_upsample_small_images() {
+        let bitmap = grey_bitmap(2, 1, &[0, 255]);
+        assert_eq!(
+            bitmap.downsample(4, 2),
+            vec![0.0, 0.0, 255.0, 255.0, 0.0, 0.0, 255.0, 255.0]
+        );
+    }
+
+    #[test]
+    fn should_fill_cells_darker_than_threshold() {
+        use FieldCell::{Empty as E, Filled as F};
+        let bitmap = grey_bitmap(4, 1, &[0, 100, 150, 255]);
+        assert_eq!(
+            bitmap.to_grid(4, 1, 128, false),
+            Grid::from_flat(4, &[F, F, E, E])
+        );
+        assert_eq!(
+            bitmap.to_grid(4, 1, 200, false),
+            Grid::from_flat(4, &[F, F, F, E])
+        );
+    }
+
+    #[test]
+    fn should_dither_mid_grey_into_pattern() {
+        let bitmap = grey_bitmap(1, 1, &[128]);
+        let undithered = bitmap.to_grid(8, 8, 128, false);
+        let dithered = bitmap.to_grid(8, 8, 128, true);
+        let count = |grid: &Grid<FieldCell>| {
+            (0..8)
+                .flat_map(|row| grid[row].to_vec())
+                .filter(|&cell| cell == FieldCell::Filled)
+                .count()
+        };
+        assert_eq!(count(&undithered), 0);
+        let filled = count(&dithered);
+        assert!((24..=40).contains(&filled), "{filled} cells filled");
+    }
+}
@@ -1,5 +1,8 @@
-use super::grid::Grid;
+use super::clues::{runs, Clues, Line};
+use super::grid::{Align, Grid};
+use super::metadata::Metadata;
 use itertools::Itertools;
+use std::ops::Range;
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -31,36 +34,83 @@ impl From<u8> for FieldCell {
     }
 }
 
+impl Grid<FieldCell> {
+    /// Filled cells become empty and vice versa
+    pub fn inverted(&self) -> Self {
+        let mut res = self.clone();
+        for row in 0..res.height() {
+            for cell in res[row].iter_mut() {
+                *cell = match cell {
+                    FieldCell::Filled => FieldCell::Empty,
+                    _ => FieldCell::Filled,
+                };
+            }
+        }
+        res
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct HintCell {
     pub number: u8,    // 0 represents empty field
     pub crossed: bool, // player can mark hints
 }
 
+/// Separates the grids in a puzzle or progress code, not part of the URL-safe
+/// base64 alphabet
+const SEPARATOR: char = '.';
+
+/// The player's fills and marks, along with the what-if cells if any
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    pub field: Grid<FieldCell>,
+    pub sandbox: Option<Grid<FieldCell>>,
+}
+
+impl Progress {
+    /// The serialized field, followed by the serialized what-if cells if any
+    pub fn serialize(&self) -> String {
+        let mut code = self.field.serialize_base64();
+        if let Some(sandbox) = &self.sandbox {
+            code.push(SEPARATOR);
+            code.push_str(&sandbox.serialize_base64());
+        }
+        code
+    }
+
+    pub fn deserialize(code: &str) -> Option<Self> {
+        let (field, sandbox) = match code.split_once(SEPARATOR) {
+            Some((field, sandbox)) => (field, Some(sandbox)),
+            None => (code, None),
+        };
+        Some(Self {
+            field: Grid::from_base64(field).ok()?,
+            sandbox: match sandbox {
+                Some(sandbox) => Some(Grid::from_base64(sandbox).ok()?),
+                None => None,
+            },
+        })
+    }
+}
+
 pub struct Board {
     width: usize,
     field: Grid<FieldCell>,
+    /// Cells tried out on top of the field while in what-if mode, `Empty`
+    /// where nothing was tried
+    sandbox: Option<Grid<FieldCell>>,
     solution: Grid<FieldCell>,
+    /// Cells revealed at the start, `Filled` or `Marked`, `Empty` if not given
+    givens: Grid<FieldCell>,
     col_hints: Grid<HintCell>,
     row_hints: Grid<HintCell>,
+    metadata: Metadata,
 }
 
 impl Board {
     fn generate_col_hints(&mut self) {
         for col in 0..self.width {
-            let counts: Vec<u8> = (0..self.width)
-                .map(|idx| self.solution[idx][col])
-                .map(|cell| (cell, 1u8))
-                .coalesce(|(cell1, count1), (cell2, count2)| {
-                    if cell1 == cell2 {
-                        Ok((cell1, count1 + count2))
-                    } else {
-                        Err(((cell1, count1), (cell2, count2)))
-                    }
-                })
-                .filter(|(cell, _)| *cell == FieldCell::Filled)
-                .map(|(_, count)| count)
-                .collect();
+            let counts = runs((0..self.width).map(|idx| &self.solution[idx][col]));
             for zip in (0..self.hint_len()).rev().zip_longest(counts.iter().rev()) {
                 match zip {
                     itertools::EitherOrBoth::Left(idx) => {
@@ -77,19 +127,7 @@ impl Board {
 
     fn generate_row_hints(&mut self) {
         for row in 0..self.width {
-            let counts: Vec<u8> = self.solution[row]
-                .iter()
-                .map(|cell| (*cell, 1u8))
-                .coalesce(|(cell1, count1), (cell2, count2)| {
-                    if cell1 == cell2 {
-                        Ok((cell1, count1 + count2))
-                    } else {
-                        Err(((cell1, count1), (cell2, count2)))
-                    }
-                })
-                .filter(|(cell, _)| *cell == FieldCell::Filled)
-                .map(|(_, count)| count)
-                .collect();
+            let counts = runs(self.solution[row].iter());
             for zip in self.row_hints[row]
                 .iter_mut()
                 .rev()
@@ -112,6 +150,23 @@ impl Board {
         self.generate_col_hints();
         self.generate_row_hints();
     }
+
+    /// Make the givens match the solution and show them on the field
+    fn sync_givens(&mut self) {
+        for row in 0..self.givens.height() {
+            for col in 0..self.givens.width() {
+                if self.givens[row][col] == FieldCell::Empty {
+                    continue;
+                }
+                let given = match self.solution[row][col] {
+                    FieldCell::Filled => FieldCell::Filled,
+                    _ => FieldCell::Marked,
+                };
+                self.givens[row][col] = given;
+                self.field[row][col] = given;
+            }
+        }
+    }
 }
 
 impl Board {
@@ -129,35 +184,117 @@ impl Board {
         let mut result = Board {
             width,
             field: Grid::new(width, width),
+            sandbox: None,
             solution: Grid::from_flat(width, &Vec::<FieldCell>::from_iter(fields)),
+            givens: Grid::new(width, width),
             col_hints: Grid::new(width, hint_len),
             row_hints: Grid::new(hint_len, height),
+            metadata: Metadata::default(),
         };
         result.generate_hints();
         result
     }
 
-    pub fn resize(&mut self, new_width: usize) {
+    /// Resize keeping the given side, corner or the centre in place
+    pub fn resize_anchored(&mut self, new_width: usize, vertical: Align, horizontal: Align) {
         self.width = new_width;
-        self.field = self.field.resized(new_width, new_width);
-        self.solution = self.solution.resized(new_width, new_width);
+        self.field = self
+            .field
+            .resized_anchored(new_width, new_width, vertical, horizontal);
+        self.sandbox = None;
+        self.solution = self
+            .solution
+            .resized_anchored(new_width, new_width, vertical, horizontal);
+        self.givens = self
+            .givens
+            .resized_anchored(new_width, new_width, vertical, horizontal);
         let hint_len = (new_width + 1) / 2;
         self.col_hints = Grid::new(new_width, hint_len);
         self.row_hints = Grid::new(hint_len, new_width);
+        self.generate_hints();
     }
 
+    /// Remove empty rows and columns around the picture, keeping the board
+    /// square and at least `min_width` wide, givens are dropped,
+    /// return false if there is no picture to crop to
+    pub fn crop_to_content(&mut self, min_width: usize) -> bool {
+        let picture = self.solution.cropped_to_content();
+        if picture.width() == 0 {
+            return false;
+        }
+        let width = picture.width().max(picture.height()).max(min_width);
+        self.set_picture(&picture.centered(width, width));
+        true
+    }
+
+    /// Add `margin` empty rows and columns on every side of the picture
+    pub fn add_margin(&mut self, margin: usize) {
+        self.resize_anchored(self.width + 2 * margin, Align::Center, Align::Center);
+    }
+
+    /// Board from a puzzle code, the serialized solution optionally followed by
+    /// the serialized givens, which may be left empty, and the metadata
     pub fn from_serialized_solution(serialized_solution: &str) -> Self {
+        let mut parts = serialized_solution.split(SEPARATOR);
+        let serialized_solution = parts.next().unwrap_or_default();
+        let serialized_givens = parts.next().unwrap_or_default();
         let solution = Grid::<FieldCell>::from_base64(serialized_solution)
             .unwrap_or_else(|_| Grid::new(10, 10));
+        let mut board = Self::from_solution(solution);
+        if let Ok(givens) = Grid::<FieldCell>::from_base64(serialized_givens) {
+            board.set_givens(&givens);
+        }
+        if let Some(metadata) = parts.next().and_then(Metadata::deserialize) {
+            board.metadata = metadata;
+        }
+        board
+    }
+
+    /// Code to share the puzzle with, see `from_serialized_solution`
+    pub fn puzzle_code(&self) -> String {
+        let mut code = self.solution.serialize_base64();
+        let has_metadata = !self.metadata.is_empty();
+        if self.has_givens() || has_metadata {
+            code.push(SEPARATOR);
+        }
+        if self.has_givens() {
+            code.push_str(&self.givens.serialize_base64());
+        }
+        if has_metadata {
+            code.push(SEPARATOR);
+            code.push_str(&self.metadata.serialize());
+        }
+        code
+    }
+
+    pub fn metadata_ref(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: Metadata) {
+        self.metadata = metadata;
+    }
+
+    /// Board with the given picture as solution, padded to a square at the right
+    /// or bottom
+    pub fn from_picture(picture: &Grid<FieldCell>) -> Self {
+        let width = std::cmp::max(picture.width(), picture.height());
+        Self::from_solution(picture.resized(width, width))
+    }
+
+    fn from_solution(solution: Grid<FieldCell>) -> Self {
         let (width, height) = (solution.width(), solution.height());
         let col_hint_len = (width + 1) / 2;
         let row_hint_len = (height + 1) / 2;
         let mut result = Board {
             width: solution.width(),
             field: Grid::new(width, height),
+            sandbox: None,
             solution,
+            givens: Grid::new(width, height),
             col_hints: Grid::new(width, col_hint_len),
             row_hints: Grid::new(row_hint_len, height),
+            metadata: Metadata::default(),
         };
         result.generate_hints();
         result
@@ -187,6 +324,19 @@ impl Board {
         self.solution[row][col]
     }
 
+    /// Whether exactly the cells of the solution are filled on the field,
+    /// marks do not matter
+    pub fn is_solved(&self) -> bool {
+        (0..self.field.height()).all(|row| {
+            self.field[row]
+                .iter()
+                .zip(self.solution[row].iter())
+                .all(|(&cell, &solution)| {
+                    (cell == FieldCell::Filled) == (solution == FieldCell::Filled)
+                })
+        })
+    }
+
     pub fn field_ref(&self) -> &Grid<FieldCell> {
         &self.field
     }
@@ -195,8 +345,74 @@ impl Board {
         &self.solution
     }
 
+    pub fn givens_ref(&self) -> &Grid<FieldCell> {
+        &self.givens
+    }
+
+    pub fn is_given(&self, row: usize, col: usize) -> bool {
+        self.givens[row][col] != FieldCell::Empty
+    }
+
+    pub fn has_givens(&self) -> bool {
+        (0..self.givens.height()).any(|row| {
+            self.givens[row]
+                .iter()
+                .any(|&cell| cell != FieldCell::Empty)
+        })
+    }
+
+    /// Reveal the cells that are not `Empty` in `givens` at the start, in place
+    /// of the current givens, ignored if the size does not match the board
+    pub fn set_givens(&mut self, givens: &Grid<FieldCell>) {
+        if givens.width() != self.solution.width() || givens.height() != self.solution.height() {
+            return;
+        }
+        for row in 0..givens.height() {
+            for col in 0..givens.width() {
+                if self.is_given(row, col) {
+                    self.field[row][col] = FieldCell::Empty;
+                }
+            }
+        }
+        self.givens = givens.clone();
+        self.sync_givens();
+    }
+
+    /// Make a cell of the solution a given or a normal cell again,
+    /// return true iff that changed anything
+    pub fn set_given(&mut self, row: usize, col: usize, given: bool) -> bool {
+        if given == self.is_given(row, col) {
+            return false;
+        }
+        if given {
+            // the actual state is filled in from the solution
+            self.givens[row][col] = FieldCell::Marked;
+            self.sync_givens();
+        } else {
+            self.givens[row][col] = FieldCell::Empty;
+            self.field[row][col] = FieldCell::Empty;
+        }
+        true
+    }
+
+    /// The cell the player changes, on the what-if layer if it is active,
+    /// None for givens and for cells decided on the field below the layer
+    fn editable_cell(&mut self, row: usize, col: usize) -> Option<&mut FieldCell> {
+        if self.is_given(row, col) {
+            return None;
+        }
+        match &mut self.sandbox {
+            Some(_) if self.field[row][col] != FieldCell::Empty => None,
+            Some(sandbox) => Some(&mut sandbox[row][col]),
+            None => Some(&mut self.field[row][col]),
+        }
+    }
+
+    /// Fill a cell, return true iff it was empty, givens cannot be changed
     pub fn fill(&mut self, row: usize, col: usize) -> bool {
-        let cell = &mut self.field[row][col];
+        let Some(cell) = self.editable_cell(row, col) else {
+            return false;
+        };
         if *cell == FieldCell::Empty {
             *cell = FieldCell::Filled;
             return true;
@@ -207,7 +423,9 @@ impl Board {
     /// Mark a cell (as known empty),
     /// return true iff the cell was not previously marked
     pub fn mark(&mut self, row: usize, col: usize) -> bool {
-        let cell = &mut self.field[row][col];
+        let Some(cell) = self.editable_cell(row, col) else {
+            return false;
+        };
         if *cell != FieldCell::Marked {
             *cell = FieldCell::Marked;
             return true;
@@ -215,11 +433,98 @@ impl Board {
         false
     }
 
+    /// Mark the remaining empty cells of every line whose filled cells match
+    /// its clue, return false if there were none
+    pub fn mark_satisfied_lines(&mut self) -> bool {
+        let clues = Clues::from_solution(&self.solution);
+        let field = self.field_with_sandbox();
+        let mut changed = false;
+        for line in clues.satisfied_lines(&field) {
+            let cells = match line {
+                Line::Row(row) => (0..self.width).map(|col| (row, col)).collect_vec(),
+                Line::Col(col) => (0..self.width).map(|row| (row, col)).collect_vec(),
+            };
+            for (row, col) in cells {
+                if field[row][col] == FieldCell::Empty {
+                    changed |= self.mark(row, col);
+                }
+            }
+        }
+        changed
+    }
+
+    pub fn progress(&self) -> Progress {
+        Progress {
+            field: self.field.clone(),
+            sandbox: self.sandbox.clone(),
+        }
+    }
+
+    /// Replace the player's progress, e.g. to undo, givens stay in place,
+    /// ignored if the size does not match the board
+    pub fn restore_progress(&mut self, progress: &Progress) {
+        let fits = |grid: &Grid<FieldCell>| {
+            grid.width() == self.field.width() && grid.height() == self.field.height()
+        };
+        if !fits(&progress.field) || !progress.sandbox.as_ref().map_or(true, fits) {
+            return;
+        }
+        self.field = progress.field.clone();
+        self.sandbox = progress.sandbox.clone();
+        self.sync_givens();
+    }
+
+    pub fn sandbox_ref(&self) -> Option<&Grid<FieldCell>> {
+        self.sandbox.as_ref()
+    }
+
+    /// The field with the what-if cells on top
+    pub fn field_with_sandbox(&self) -> Grid<FieldCell> {
+        let mut field = self.field.clone();
+        if let Some(sandbox) = &self.sandbox {
+            for row in 0..field.height() {
+                for col in 0..field.width() {
+                    if field[row][col] == FieldCell::Empty {
+                        field[row][col] = sandbox[row][col];
+                    }
+                }
+            }
+        }
+        field
+    }
+
+    /// Enter what-if mode, following changes go to a layer on top of the
+    /// field, return false if already in it
+    pub fn start_sandbox(&mut self) -> bool {
+        if self.sandbox.is_some() {
+            return false;
+        }
+        self.sandbox = Some(Grid::new(self.field.width(), self.field.height()));
+        true
+    }
+
+    /// Leave what-if mode keeping the cells tried out, return false if not in it
+    pub fn commit_sandbox(&mut self) -> bool {
+        if self.sandbox.is_none() {
+            return false;
+        }
+        self.field = self.field_with_sandbox();
+        self.sandbox = None;
+        true
+    }
+
+    /// Leave what-if mode dropping the cells tried out, return false if not in it
+    pub fn discard_sandbox(&mut self) -> bool {
+        self.sandbox.take().is_some()
+    }
+
     /// Remove mark from a cell (leaving it as empty),
     /// return true iff the cell was previously marked,
     /// no-op if the cell was filled or empty
     pub fn unmark(&mut self, row: usize, col: usize) -> bool {
-        let cell = &mut self.field[row][col];
+        let Some(cell) = self.editable_cell(row, col) else {
+            return false;
+        };
         if *cell == FieldCell::Marked {
             *cell = FieldCell::Empty;
             return true;
@@ -227,6 +532,48 @@ impl Board {
         false
     }
 
+    /// Replace the solution by a transformed version of it, the board grows
+    /// to a square if needed and the hints are generated anew, givens are
+    /// dropped
+    pub fn transform(&mut self, transform: impl FnOnce(&Grid<FieldCell>) -> Grid<FieldCell>) {
+        self.set_picture(&transform(&self.solution));
+    }
+
+    /// Replace the solution by another picture like `from_picture` does,
+    /// keeping only the metadata
+    pub fn set_picture(&mut self, picture: &Grid<FieldCell>) {
+        let metadata = std::mem::take(&mut self.metadata);
+        *self = Self::from_picture(picture);
+        self.metadata = metadata;
+    }
+
+    /// Copy a picture into the solution with its top left corner at
+    /// (`row`, `col`), parts outside the board are dropped,
+    /// return true iff the solution was changed
+    pub fn set_region(&mut self, row: isize, col: isize, picture: &Grid<FieldCell>) -> bool {
+        let old_solution = self.solution.clone();
+        self.solution.paste(picture, row, col);
+        if self.solution == old_solution {
+            return false;
+        }
+        self.generate_hints();
+        self.sync_givens();
+        true
+    }
+
+    /// Empty the cells of the solution within the given rows and columns,
+    /// return true iff the solution was changed
+    pub fn clear_region(&mut self, rows: Range<usize>, cols: Range<usize>) -> bool {
+        let old_solution = self.solution.clone();
+        self.solution.fill_region(rows, cols, FieldCell::Empty);
+        if self.solution == old_solution {
+            return false;
+        }
+        self.generate_hints();
+        self.sync_givens();
+        true
+    }
+
     /// Set a cell in the solution,
     /// return true iff the solution was changed
     pub fn set(&mut self, row: usize, col: usize, filled: bool) -> bool {
@@ -239,8 +586,143 @@ impl Board {
         if *cell != target_val {
             *cell = target_val;
             self.generate_hints();
+            self.sync_givens();
             return true;
         }
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FieldCell::{Empty as E, Filled as F, Marked as X};
+
+    #[test]
+    fn puzzle_code_without_givens_should_be_the_serialized_solution() {
+        let board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        assert_eq!(board.puzzle_code(), board.solution_ref().serialize_base64());
+    }
+
+    #[test]
+    fn givens_should_survive_puzzle_code() {
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.set_given(0, 0, true);
+        board.set_given(0, 1, true);
+        let board = Board::from_serialized_solution(&board.puzzle_code());
+        assert_eq!(board.givens_ref(), &Grid::from_flat(2, &[F, X, E, E]));
+        assert_eq!(board.field_ref(), &Grid::from_flat(2, &[F, X, E, E]));
+    }
+
+    #[test]
+    fn metadata_should_survive_puzzle_code_and_picture_changes() {
+        let metadata = Metadata {
+            title: "Diagonal".to_owned(),
+            ..Metadata::default()
+        };
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.set_metadata(metadata.clone());
+        let mut board = Board::from_serialized_solution(&board.puzzle_code());
+        assert_eq!(board.metadata_ref(), &metadata);
+        assert!(!board.has_givens());
+        board.set_given(0, 0, true);
+        let mut board = Board::from_serialized_solution(&board.puzzle_code());
+        assert_eq!(board.metadata_ref(), &metadata);
+        assert!(board.is_given(0, 0));
+        board.transform(|picture| picture.clone());
+        assert_eq!(board.metadata_ref(), &metadata);
+    }
+
+    #[test]
+    fn inverted_picture_should_swap_filled_and_empty_cells() {
+        let picture = Grid::from_flat(2, &[F, E, X, F]);
+        assert_eq!(picture.inverted(), Grid::from_flat(2, &[E, F, F, E]));
+        assert_eq!(
+            picture.inverted().inverted(),
+            Grid::from_flat(2, &[F, E, E, F])
+        );
+    }
+
+    #[test]
+    fn should_be_solved_when_filled_cells_match() {
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.fill(0, 0);
+        assert!(!board.is_solved());
+        board.mark(0, 1);
+        board.fill(1, 1);
+        assert!(board.is_solved());
+        board.fill(1, 0);
+        assert!(!board.is_solved());
+    }
+
+    #[test]
+    fn should_mark_rest_of_satisfied_lines() {
+        let picture = Grid::from_flat(3, &[F, E, E, E, E, E, E, E, F]);
+        let mut board = Board::from_picture(&picture);
+        board.fill(0, 0);
+        assert!(board.mark_satisfied_lines());
+        let expected = Grid::from_flat(3, &[F, X, X, X, X, X, X, X, E]);
+        assert_eq!(board.field_ref(), &expected);
+        assert!(!board.mark_satisfied_lines());
+    }
+
+    #[test]
+    fn should_keep_what_if_cells_apart_until_committed() {
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.fill(0, 0);
+        assert!(board.start_sandbox());
+        assert!(board.fill(0, 1));
+        assert!(board.mark(1, 0));
+        assert!(!board.mark(0, 0));
+        assert_eq!(board.field_ref(), &Grid::from_flat(2, &[F, E, E, E]));
+        assert_eq!(
+            board.field_with_sandbox(),
+            Grid::from_flat(2, &[F, F, X, E])
+        );
+        assert!(board.commit_sandbox());
+        assert_eq!(board.field_ref(), &Grid::from_flat(2, &[F, F, X, E]));
+        assert!(board.sandbox_ref().is_none());
+    }
+
+    #[test]
+    fn should_drop_discarded_what_if_cells() {
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.start_sandbox();
+        board.fill(1, 1);
+        assert!(board.discard_sandbox());
+        assert_eq!(board.field_ref(), &Grid::new(2, 2));
+        assert!(!board.discard_sandbox());
+    }
+
+    #[test]
+    fn progress_should_survive_serialization() {
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.fill(0, 0);
+        let progress = board.progress();
+        assert_eq!(Progress::deserialize(&progress.serialize()), Some(progress));
+        board.start_sandbox();
+        board.mark(1, 1);
+        let progress = board.progress();
+        assert_eq!(Progress::deserialize(&progress.serialize()), Some(progress));
+        assert_eq!(Progress::deserialize("garbage"), None);
+    }
+
+    #[test]
+    fn givens_should_be_locked() {
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.set_given(0, 1, true);
+        assert!(!board.fill(0, 1));
+        assert!(!board.unmark(0, 1));
+        assert!(board.fill(1, 1));
+        assert_eq!(board.field(0, 1), X);
+    }
+
+    #[test]
+    fn givens_should_follow_changes_to_the_solution() {
+        let mut board = Board::from_picture(&Grid::from_flat(2, &[F, E, E, F]));
+        board.set_given(0, 0, true);
+        board.set(0, 0, false);
+        assert_eq!(board.givens_ref()[0][0], X);
+        assert_eq!(board.field(0, 0), X);
+    }
+}
@@ -0,0 +1,253 @@
+use super::board::FieldCell;
+use super::grid::Grid;
+use itertools::Itertools;
+
+/// A row or column of the board
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Line {
+    Row(usize),
+    Col(usize),
+}
+
+impl std::fmt::Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Line::Row(idx) => write!(f, "Row {}", idx + 1),
+            Line::Col(idx) => write!(f, "Column {}", idx + 1),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ClueError {
+    /// Clue text could not be read as a list of numbers
+    Invalid(Line, String),
+    /// (line, cells needed by runs and gaps, line length)
+    TooLong(Line, usize, usize),
+    /// (filled cells according to rows, according to columns)
+    TotalsDiffer(usize, usize),
+}
+
+impl std::fmt::Display for ClueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClueError::Invalid(line, text) => write!(f, "{line}: cannot read '{text}'"),
+            ClueError::TooLong(line, needed, len) => {
+                write!(f, "{line}: clue needs {needed} cells, only {len} available")
+            }
+            ClueError::TotalsDiffer(rows, cols) => write!(
+                f,
+                "Rows fill {rows} cells in total, but columns fill {cols}"
+            ),
+        }
+    }
+}
+
+/// Lengths of the runs of filled cells for every row and column
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Clues {
+    pub rows: Vec<Vec<u8>>,
+    pub cols: Vec<Vec<u8>>,
+}
+
+/// Lengths of the runs of filled cells in a line
+pub fn runs<'a>(line: impl Iterator<Item = &'a FieldCell>) -> Vec<u8> {
+    line.map(|cell| (*cell, 1u8))
+        .coalesce(|(cell1, count1), (cell2, count2)| {
+            if cell1 == cell2 {
+                Ok((cell1, count1 + count2))
+            } else {
+                Err(((cell1, count1), (cell2, count2)))
+            }
+        })
+        .filter(|(cell, _)| *cell == FieldCell::Filled)
+        .map(|(_, count)| count)
+        .collect()
+}
+
+/// Read a clue written as numbers separated by spaces or commas, "0" or nothing
+/// stand for an empty line
+pub fn parse_clue(text: &str) -> Option<Vec<u8>> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|num| !num.is_empty())
+        .map(|num| num.parse::<u8>().ok())
+        .filter(|num| *num != Some(0))
+        .collect()
+}
+
+/// Cells needed to place the runs with single gaps in between
+pub fn min_line_len(clue: &[u8]) -> usize {
+    clue.iter().map(|&run| run as usize).sum::<usize>() + clue.len().saturating_sub(1)
+}
+
+impl Clues {
+    pub fn from_solution(solution: &Grid<FieldCell>) -> Self {
+        Self {
+            rows: (0..solution.height())
+                .map(|row| runs(solution[row].iter()))
+                .collect(),
+            cols: (0..solution.width())
+                .map(|col| runs((0..solution.height()).map(|row| &solution[row][col])))
+                .collect(),
+        }
+    }
+
+    /// Read clues typed per line, see `parse_clue`
+    pub fn parse<S: AsRef<str>>(rows: &[S], cols: &[S]) -> Result<Self, ClueError> {
+        let parse_all = |texts: &[S], line: fn(usize) -> Line| {
+            texts
+                .iter()
+                .enumerate()
+                .map(|(idx, text)| {
+                    parse_clue(text.as_ref())
+                        .ok_or_else(|| ClueError::Invalid(line(idx), text.as_ref().to_owned()))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            rows: parse_all(rows, Line::Row)?,
+            cols: parse_all(cols, Line::Col)?,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn line(&self, line: Line) -> &[u8] {
+        match line {
+            Line::Row(idx) => &self.rows[idx],
+            Line::Col(idx) => &self.cols[idx],
+        }
+    }
+
+    /// All rows followed by all columns
+    pub fn lines(&self) -> impl Iterator<Item = Line> {
+        (0..self.height())
+            .map(Line::Row)
+            .chain((0..self.width()).map(Line::Col))
+    }
+
+    /// Lines whose filled cells on the grid form exactly the runs of their clue
+    pub fn satisfied_lines(&self, grid: &Grid<FieldCell>) -> Vec<Line> {
+        self.lines()
+            .filter(|&line| {
+                let filled = match line {
+                    Line::Row(row) => runs(grid[row].iter()),
+                    Line::Col(col) => runs((0..grid.height()).map(|row| &grid[row][col])),
+                };
+                filled == self.line(line)
+            })
+            .collect()
+    }
+
+    /// Check that every clue fits its line and rows and columns agree on the
+    /// number of filled cells
+    pub fn validate(&self) -> Result<(), ClueError> {
+        for line in self.lines() {
+            let len = match line {
+                Line::Row(_) => self.width(),
+                Line::Col(_) => self.height(),
+            };
+            let needed = min_line_len(self.line(line));
+            if needed > len {
+                return Err(ClueError::TooLong(line, needed, len));
+            }
+        }
+        let total = |clues: &[Vec<u8>]| {
+            clues
+                .iter()
+                .flatten()
+                .map(|&run| run as usize)
+                .sum::<usize>()
+        };
+        let (rows_total, cols_total) = (total(&self.rows), total(&self.cols));
+        if rows_total != cols_total {
+            return Err(ClueError::TotalsDiffer(rows_total, cols_total));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FieldCell::{Empty as E, Filled as F, Marked as X};
+
+    #[test]
+    fn should_parse_numbers_separated_by_spaces_or_commas() {
+        assert_eq!(parse_clue("3 1 2"), Some(vec![3, 1, 2]));
+        assert_eq!(parse_clue(" 3,1,  2 "), Some(vec![3, 1, 2]));
+    }
+
+    #[test]
+    fn should_parse_empty_line_clue() {
+        assert_eq!(parse_clue(""), Some(vec![]));
+        assert_eq!(parse_clue("0"), Some(vec![]));
+    }
+
+    #[test]
+    fn should_reject_garbage_clue() {
+        assert_eq!(parse_clue("3 a"), None);
+        assert_eq!(parse_clue("-1"), None);
+        assert_eq!(parse_clue("300"), None);
+    }
+
+    #[test]
+    fn should_compute_minimal_line_length() {
+        assert_eq!(min_line_len(&[]), 0);
+        assert_eq!(min_line_len(&[4]), 4);
+        assert_eq!(min_line_len(&[3, 1, 2]), 8);
+    }
+
+    #[test]
+    fn should_derive_clues_from_solution() {
+        let solution = Grid::from_flat(3, &[F, E, F, F, F, F, E, E, E]);
+        let clues = Clues::from_solution(&solution);
+        assert_eq!(clues.rows, vec![vec![1, 1], vec![3], vec![]]);
+        assert_eq!(clues.cols, vec![vec![2], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn should_find_satisfied_lines() {
+        let clues = Clues::from_solution(&Grid::from_flat(2, &[F, E, F, F]));
+        let grid = Grid::from_flat(2, &[F, X, E, F]);
+        assert_eq!(
+            clues.satisfied_lines(&grid),
+            vec![Line::Row(0), Line::Col(1)]
+        );
+    }
+
+    #[test]
+    fn should_report_unreadable_clue() {
+        assert_eq!(
+            Clues::parse(&["1", "x"], &["1", ""]),
+            Err(ClueError::Invalid(Line::Row(1), "x".to_owned()))
+        );
+    }
+
+    #[test]
+    fn should_accept_valid_clues() {
+        let clues = Clues::parse(&["1 1", "3", ""], &["2", "1", "2"]).unwrap();
+        assert_eq!(clues.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_reject_clue_longer_than_line() {
+        let clues = Clues::parse(&["2 1", "", ""], &["1", "1", "1"]).unwrap();
+        assert_eq!(
+            clues.validate(),
+            Err(ClueError::TooLong(Line::Row(0), 4, 3))
+        );
+    }
+
+    #[test]
+    fn should_reject_differing_totals() {
+        let clues = Clues::parse(&["3", "", ""], &["1", "1", ""]).unwrap();
+        assert_eq!(clues.validate(), Err(ClueError::TotalsDiffer(3, 2)));
+    }
+}
@@ -0,0 +1,298 @@
+//! Chains of line deductions showing why a cell has to be filled or empty
+
+use super::board::FieldCell;
+use super::clues::{Clues, Line};
+use super::grid::Grid;
+use super::solver::{
+    deduce_line, leftmost_placement, line_position, rightmost_placement, solve_line, Contradiction,
+};
+use itertools::Itertools;
+
+/// One application of the line solver to a row or column
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub line: Line,
+    pub clue: Vec<u8>,
+    /// Known cells the deduction relies on, as (row, col, state)
+    pub premises: Vec<(usize, usize, FieldCell)>,
+    /// First and last cell along the line each run lies within in every
+    /// placement consistent with the premises
+    pub spans: Vec<(usize, usize)>,
+    /// Deduced cells needed later on, as (row, col, state)
+    pub conclusions: Vec<(usize, usize, FieldCell)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Explanation {
+    /// The cell is filled or marked on the field already
+    Known,
+    /// Steps in order, the last one determines the cell
+    Steps(Vec<Step>),
+    /// The line solver alone cannot determine the cell from the field
+    Undetermined,
+    /// The field does not match the clues
+    Contradiction(Option<Line>),
+}
+
+/// Index of a cell along a line running through it
+fn line_index(line: Line, (row, col): (usize, usize)) -> usize {
+    match line {
+        Line::Row(_) => col,
+        Line::Col(_) => row,
+    }
+}
+
+/// Why the line solver determines a cell when starting from the field, with
+/// `Empty` cells unknown and `Marked` cells known to be empty
+pub fn explain_cell(
+    clues: &Clues,
+    field: &Grid<FieldCell>,
+    (row, col): (usize, usize),
+) -> Explanation {
+    if field[row][col] != FieldCell::Empty {
+        return Explanation::Known;
+    }
+    let mut grid = field.clone();
+    // index of the deduction each cell was found by
+    let mut origin = Grid::<Option<usize>>::new(grid.width(), grid.height());
+    let mut lines = vec![];
+    'passes: loop {
+        let mut changed = false;
+        for line in clues.lines() {
+            let deduced = match deduce_line(clues, &mut grid, line) {
+                Ok(deduced) => deduced,
+                Err(Contradiction(line)) => return Explanation::Contradiction(line),
+            };
+            if deduced.is_empty() {
+                continue;
+            }
+            for (r, c, _) in deduced {
+                origin[r][c] = Some(lines.len());
+            }
+            lines.push(line);
+            changed = true;
+            if origin[row][col].is_some() {
+                break 'passes;
+            }
+        }
+        if !changed {
+            return Explanation::Undetermined;
+        }
+    }
+
+    // follow the deductions back to the field, keeping only what is needed
+    let mut needed = vec![vec![]; lines.len()];
+    needed[lines.len() - 1].push((row, col));
+    let mut steps = vec![];
+    for (step, &line) in lines.iter().enumerate().rev() {
+        let mut targets = std::mem::take(&mut needed[step]);
+        if targets.is_empty() {
+            continue;
+        }
+        targets.sort_unstable();
+        targets.dedup();
+        let clue = clues.line(line);
+        let n = match line {
+            Line::Row(_) => grid.width(),
+            Line::Col(_) => grid.height(),
+        };
+        let mut known = (0..n)
+            .map(|idx| {
+                let (r, c) = line_position(line, idx);
+                let known_before = field[r][c] != FieldCell::Empty
+                    || origin[r][c].is_some_and(|found_by| found_by < step);
+                if known_before {
+                    grid[r][c]
+                } else {
+                    FieldCell::Empty
+                }
+            })
+            .collect::<Vec<_>>();
+        let deduces_targets = |known: &[FieldCell]| {
+            solve_line(clue, known).is_some_and(|solved| {
+                targets
+                    .iter()
+                    .all(|&(r, c)| solved[line_index(line, (r, c))] == grid[r][c])
+            })
+        };
+        for idx in 0..n {
+            let state = known[idx];
+            if state == FieldCell::Empty {
+                continue;
+            }
+            known[idx] = FieldCell::Empty;
+            if !deduces_targets(&known) {
+                known[idx] = state;
+            }
+        }
+        let premises = (0..n)
+            .filter(|&idx| known[idx] != FieldCell::Empty)
+            .map(|idx| {
+                let (r, c) = line_position(line, idx);
+                if let Some(found_by) = origin[r][c] {
+                    needed[found_by].push((r, c));
+                }
+                (r, c, known[idx])
+            })
+            .collect();
+        let spans = match (
+            leftmost_placement(clue, &known),
+            rightmost_placement(clue, &known),
+        ) {
+            (Some(left), Some(right)) => left
+                .iter()
+                .zip(right.iter())
+                .zip(clue.iter())
+                .map(|((&first, &last_start), &len)| (first, last_start + len as usize - 1))
+                .collect(),
+            _ => vec![],
+        };
+        steps.push(Step {
+            line,
+            clue: clue.to_vec(),
+            premises,
+            spans,
+            conclusions: targets.iter().map(|&(r, c)| (r, c, grid[r][c])).collect(),
+        });
+    }
+    steps.reverse();
+    Explanation::Steps(steps)
+}
+
+/// What the cells along a line are called
+fn cell_kind(line: Line) -> &'static str {
+    match line {
+        Line::Row(_) => "column",
+        Line::Col(_) => "row",
+    }
+}
+
+/// Cells along a line, e.g. "columns 2, 3 and 5"
+fn cells_text(line: Line, indices: &[usize]) -> String {
+    let kind = cell_kind(line);
+    match indices {
+        [single] => format!("{kind} {}", single + 1),
+        [init @ .., last] => {
+            let init = init.iter().map(|idx| idx + 1).join(", ");
+            format!("{kind}s {init} and {}", last + 1)
+        }
+        [] => String::new(),
+    }
+}
+
+/// Cells from `first` to `last` along a line, e.g. "rows 2–4"
+fn span_text(line: Line, first: usize, last: usize) -> String {
+    match first == last {
+        true => cells_text(line, &[first]),
+        false => format!("{}s {}–{}", cell_kind(line), first + 1, last + 1),
+    }
+}
+
+/// Cells grouped by state, e.g. "column 2 filled and columns 4 and 5 empty", or
+/// "... is filled ... are empty" as a statement
+fn states_text(line: Line, cells: &[(usize, usize, FieldCell)], statement: bool) -> String {
+    [(FieldCell::Filled, "filled"), (FieldCell::Marked, "empty")]
+        .iter()
+        .filter_map(|&(state, word)| {
+            let indices = cells
+                .iter()
+                .filter(|&&(_, _, cell)| cell == state)
+                .map(|&(r, c, _)| line_index(line, (r, c)))
+                .collect::<Vec<_>>();
+            let verb = match (statement, indices.len()) {
+                (false, _) => "",
+                (true, 1) => " is",
+                (true, _) => " are",
+            };
+            (!indices.is_empty()).then(|| format!("{}{verb} {word}", cells_text(line, &indices)))
+        })
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let clue = match self.clue.is_empty() {
+            true => "0".to_owned(),
+            false => self.clue.iter().map(|len| len.to_string()).join(" "),
+        };
+        write!(f, "{} (clue {clue}): ", self.line)?;
+        if !self.premises.is_empty() {
+            write!(
+                f,
+                "with {}, ",
+                states_text(self.line, &self.premises, false)
+            )?;
+        }
+        if self.clue.is_empty() {
+            write!(f, "there are no filled cells")?;
+        } else {
+            let spans = self
+                .clue
+                .iter()
+                .zip(self.spans.iter())
+                .map(|(len, &(first, last))| {
+                    format!(
+                        "the {len} lies within {}",
+                        span_text(self.line, first, last)
+                    )
+                })
+                .join(", ");
+            write!(f, "{spans}")?;
+        }
+        write!(
+            f,
+            ", so {}",
+            states_text(self.line, &self.conclusions, true)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FieldCell::{Empty as U, Filled as F, Marked as X};
+
+    fn clues() -> Clues {
+        Clues::from_solution(&Grid::from_flat(3, &[F, F, F, U, F, U, U, F, U]))
+    }
+
+    #[test]
+    fn should_not_explain_known_cell() {
+        let field = Grid::from_flat(3, &[F, U, U, U, U, U, U, U, U]);
+        assert_eq!(explain_cell(&clues(), &field, (0, 0)), Explanation::Known);
+    }
+
+    #[test]
+    fn should_explain_cell_through_chain_of_lines() {
+        let Explanation::Steps(steps) = explain_cell(&clues(), &Grid::new(3, 3), (1, 0)) else {
+            panic!("Cell should be determined");
+        };
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].line, Line::Row(0));
+        assert_eq!(steps[0].conclusions, vec![(0, 0, F)]);
+        assert_eq!(steps[1].line, Line::Col(0));
+        assert_eq!(steps[1].premises, vec![(0, 0, F)]);
+        assert_eq!(steps[1].conclusions, vec![(1, 0, X)]);
+        assert_eq!(
+            steps[1].to_string(),
+            "Column 1 (clue 1): with row 1 filled, the 1 lies within row 1, so row 2 is empty"
+        );
+    }
+
+    #[test]
+    fn should_report_undetermined_cell() {
+        let clues = Clues::from_solution(&Grid::from_flat(2, &[F, U, U, F]));
+        let explanation = explain_cell(&clues, &Grid::new(2, 2), (0, 0));
+        assert_eq!(explanation, Explanation::Undetermined);
+    }
+
+    #[test]
+    fn should_report_contradicting_field() {
+        let field = Grid::from_flat(3, &[U, U, U, F, U, U, U, U, U]);
+        assert_eq!(
+            explain_cell(&clues(), &field, (2, 2)),
+            Explanation::Contradiction(Some(Line::Col(0)))
+        );
+    }
+}
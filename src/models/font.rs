@@ -0,0 +1,159 @@
+use super::board::FieldCell;
+use super::grid::Grid;
+
+pub const GLYPH_WIDTH: usize = 5;
+pub const GLYPH_HEIGHT: usize = 7;
+const LETTER_SPACING: usize = 1;
+const LINE_SPACING: usize = 1;
+
+type Glyph = [u8; GLYPH_HEIGHT];
+
+/// 5x7 pixel glyphs, one byte per row, most significant of the five bits is the
+/// leftmost pixel
+#[rustfmt::skip]
+const GLYPHS: &[(char, Glyph)] = &[
+    (' ', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('A', [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('B', [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+    ('C', [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('D', [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110]),
+    ('E', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('F', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('G', [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+    ('H', [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('I', [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('J', [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('K', [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+    ('L', [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+    ('M', [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+    ('N', [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+    ('O', [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('P', [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('Q', [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+    ('R', [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+    ('S', [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+    ('T', [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('U', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('V', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('W', [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+    ('X', [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+    ('Y', [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('Z', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+    ('0', [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+    ('1', [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('2', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('3', [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+    ('4', [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+    ('5', [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+    ('6', [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+    ('7', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+    ('8', [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+    ('9', [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+    ('!', [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100]),
+    ('?', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100]),
+    ('.', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100]),
+    (',', [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000]),
+    (':', [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000]),
+    ('-', [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000]),
+    ('+', [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000]),
+    ('\'', [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('♥', [0b00000, 0b01010, 0b11111, 0b11111, 0b01110, 0b00100, 0b00000]),
+];
+
+/// Glyph for a character, lower case letters are drawn as upper case, unknown
+/// characters as '?'
+fn glyph(c: char) -> &'static Glyph {
+    let c = c.to_ascii_uppercase();
+    GLYPHS
+        .iter()
+        .find(|(glyph_char, _)| *glyph_char == c)
+        .or_else(|| GLYPHS.iter().find(|(glyph_char, _)| *glyph_char == '?'))
+        .map(|(_, glyph)| glyph)
+        .expect("Font is missing the '?' glyph")
+}
+
+/// Draw each line of `text` with the built-in font, lines are centred
+/// horizontally against each other, the picture has no blank border
+pub fn render_text(text: &str) -> Grid<FieldCell> {
+    let lines = text
+        .lines()
+        .map(|line| line.trim().chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let line_width =
+        |n_chars: usize| (n_chars * (GLYPH_WIDTH + LETTER_SPACING)).saturating_sub(LETTER_SPACING);
+    let width = lines.iter().map(|l| line_width(l.len())).max().unwrap_or(0);
+    let height = (lines.len() * (GLYPH_HEIGHT + LINE_SPACING)).saturating_sub(LINE_SPACING);
+    let mut grid = Grid::new(width, height);
+    for (line_idx, line) in lines.iter().enumerate() {
+        let y0 = line_idx * (GLYPH_HEIGHT + LINE_SPACING);
+        let indent = (width - line_width(line.len())) / 2;
+        for (char_idx, c) in line.iter().enumerate() {
+            let x0 = indent + char_idx * (GLYPH_WIDTH + LETTER_SPACING);
+            for (dy, bits) in glyph(*c).iter().enumerate() {
+                for dx in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - dx)) != 0 {
+                        grid[y0 + dy][x0 + dx] = FieldCell::Filled;
+                    }
+                }
+            }
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ascii_art::TextFormat;
+
+    fn as_text(grid: &Grid<FieldCell>) -> String {
+        grid.to_text(&TextFormat::default())
+    }
+
+    #[test]
+    fn should_render_nothing_for_empty_text() {
+        let grid = render_text("");
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid.height(), 0);
+    }
+
+    #[test]
+    fn should_render_single_glyph() {
+        let expected =
+            concat!(".###.\n", "#...#\n", "#...#\n", "#####\n", "#...#\n", "#...#\n", "#...#\n",);
+        assert_eq!(as_text(&render_text("A")), expected);
+    }
+
+    #[test]
+    fn should_treat_lower_case_as_upper_case() {
+        assert_eq!(render_text("hi"), render_text("HI"));
+    }
+
+    #[test]
+    fn should_draw_unknown_characters_as_question_mark() {
+        assert_eq!(render_text("~"), render_text("?"));
+    }
+
+    #[test]
+    fn should_separate_glyphs_by_blank_column() {
+        let grid = render_text("II");
+        assert_eq!(grid.width(), 2 * GLYPH_WIDTH + 1);
+        assert_eq!(grid.height(), GLYPH_HEIGHT);
+        for row in 0..GLYPH_HEIGHT {
+            assert_eq!(grid[row][GLYPH_WIDTH], FieldCell::Empty);
+        }
+    }
+
+    #[test]
+    fn should_stack_and_centre_lines() {
+        let grid = render_text("HI\n1");
+        assert_eq!(grid.width(), 11);
+        assert_eq!(grid.height(), 2 * GLYPH_HEIGHT + 1);
+        assert!(grid[GLYPH_HEIGHT].iter().all(|&c| c == FieldCell::Empty));
+        // '1' is centred below "HI", its stem is the middle column
+        let stem = (GLYPH_HEIGHT + 1..grid.height())
+            .filter(|&row| grid[row][5] == FieldCell::Filled)
+            .count();
+        assert_eq!(stem, GLYPH_HEIGHT);
+    }
+}
@@ -1,4 +1,25 @@
 use itertools::Itertools;
+use std::ops::Range;
+
+/// Where existing cells stay when a grid is resized along one dimension
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    /// Offset of the old cells inside the new length, negative when cropping
+    fn offset(&self, old_len: usize, new_len: usize) -> isize {
+        let diff = new_len as isize - old_len as isize;
+        match self {
+            Align::Start => 0,
+            Align::Center => diff / 2,
+            Align::End => diff,
+        }
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Grid<T> {
@@ -63,6 +84,182 @@ impl<T: Default + Clone + Copy> Grid<T> {
             cells,
         }
     }
+    /// Like `resized`, but padding or cropping evenly on all sides
+    pub fn centered(&self, new_width: usize, new_height: usize) -> Self {
+        self.resized_anchored(new_width, new_height, Align::Center, Align::Center)
+    }
+
+    /// Like `resized`, but keeping the cells at the given side or centre in
+    /// place, padding or cropping at the others
+    pub fn resized_anchored(
+        &self,
+        new_width: usize,
+        new_height: usize,
+        vertical: Align,
+        horizontal: Align,
+    ) -> Self {
+        self.placed(
+            new_width,
+            new_height,
+            vertical.offset(self.height(), new_height),
+            horizontal.offset(self.width, new_width),
+        )
+    }
+
+    /// Grid of the given size with this grid's cells moved down by
+    /// `row_offset` and right by `col_offset`, cells moved outside are dropped
+    pub fn placed(
+        &self,
+        new_width: usize,
+        new_height: usize,
+        row_offset: isize,
+        col_offset: isize,
+    ) -> Self {
+        let mut res = Self::new(new_width, new_height);
+        for row in 0..new_height {
+            let src_row = row as isize - row_offset;
+            if src_row < 0 || src_row as usize >= self.height() {
+                continue;
+            }
+            for col in 0..new_width {
+                let src_col = col as isize - col_offset;
+                if src_col >= 0 && (src_col as usize) < self.width {
+                    res[row][col] = self[src_row as usize][src_col as usize];
+                }
+            }
+        }
+        res
+    }
+
+    /// The cells within the given rows and columns
+    pub fn sub_grid(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        self.placed(
+            cols.len(),
+            rows.len(),
+            -(rows.start as isize),
+            -(cols.start as isize),
+        )
+    }
+
+    /// Copy the cells of `other` into this grid with its top left corner at
+    /// (`row`, `col`), cells falling outside are dropped
+    pub fn paste(&mut self, other: &Self, row: isize, col: isize) {
+        for src_row in 0..other.height() {
+            for src_col in 0..other.width {
+                let (dst_row, dst_col) = (row + src_row as isize, col + src_col as isize);
+                if dst_row >= 0
+                    && dst_col >= 0
+                    && (dst_row as usize) < self.height()
+                    && (dst_col as usize) < self.width
+                {
+                    self[dst_row as usize][dst_col as usize] = other[src_row][src_col];
+                }
+            }
+        }
+    }
+
+    /// Set all cells within the given rows and columns to `value`
+    pub fn fill_region(&mut self, rows: Range<usize>, cols: Range<usize>, value: T) {
+        for row in rows {
+            self[row][cols.clone()].fill(value);
+        }
+    }
+
+    /// Grid of the given size where every cell is copied from the position
+    /// returned by `source`, or left at default if it returns None
+    fn remapped(
+        &self,
+        new_width: usize,
+        new_height: usize,
+        source: impl Fn(usize, usize) -> Option<(usize, usize)>,
+    ) -> Self {
+        let mut res = Self::new(new_width, new_height);
+        for row in 0..new_height {
+            for col in 0..new_width {
+                if let Some((src_row, src_col)) = source(row, col) {
+                    res[row][col] = self[src_row][src_col];
+                }
+            }
+        }
+        res
+    }
+
+    /// Mirrored left to right
+    pub fn flipped_horizontally(&self) -> Self {
+        let width = self.width;
+        self.remapped(width, self.height(), |row, col| {
+            Some((row, width - 1 - col))
+        })
+    }
+
+    /// Mirrored top to bottom
+    pub fn flipped_vertically(&self) -> Self {
+        let height = self.height();
+        self.remapped(self.width, height, |row, col| Some((height - 1 - row, col)))
+    }
+
+    /// Mirrored along the main diagonal, swapping width and height
+    pub fn transposed(&self) -> Self {
+        self.remapped(self.height(), self.width, |row, col| Some((col, row)))
+    }
+
+    /// Rotated by 90°, swapping width and height
+    pub fn rotated_clockwise(&self) -> Self {
+        let height = self.height();
+        self.remapped(height, self.width, |row, col| Some((height - 1 - col, row)))
+    }
+
+    /// Rotated by 90°, swapping width and height
+    pub fn rotated_counterclockwise(&self) -> Self {
+        let width = self.width;
+        self.remapped(self.height(), width, |row, col| {
+            Some((col, width - 1 - row))
+        })
+    }
+
+    /// Moved down by `rows` and right by `cols` (up and left if negative),
+    /// cells moved out on one side come back in on the other if `cyclic`,
+    /// otherwise they are dropped and default cells move in
+    pub fn shifted(&self, rows: isize, cols: isize, cyclic: bool) -> Self {
+        let (width, height) = (self.width as isize, self.height() as isize);
+        let source = |idx: usize, by: isize, len: isize| {
+            let src = idx as isize - by;
+            if cyclic {
+                Some(src.rem_euclid(len) as usize)
+            } else {
+                (0..len).contains(&src).then_some(src as usize)
+            }
+        };
+        self.remapped(self.width, self.height(), |row, col| {
+            Some((source(row, rows, height)?, source(col, cols, width)?))
+        })
+    }
+}
+
+impl<T: Default + Clone + Copy + PartialEq> Grid<T> {
+    /// Rows and columns holding cells other than the default, None if there
+    /// are none
+    pub fn content_bounds(&self) -> Option<(Range<usize>, Range<usize>)> {
+        let is_content = |cell: &T| *cell != T::default();
+        let rows = (0..self.height())
+            .filter(|&row| self[row].iter().any(is_content))
+            .collect_vec();
+        let cols = (0..self.width)
+            .filter(|&col| (0..self.height()).any(|row| is_content(&self[row][col])))
+            .collect_vec();
+        Some((
+            *rows.first()?..*rows.last()? + 1,
+            *cols.first()?..*cols.last()? + 1,
+        ))
+    }
+
+    /// Without border rows and columns holding only default cells
+    pub fn cropped_to_content(&self) -> Self {
+        match self.content_bounds() {
+            Some((rows, cols)) => self.sub_grid(rows, cols),
+            None => Self::new(0, 0),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -241,6 +438,134 @@ mod tests {
         assert_eq!(serialized, "AgACAA==5A");
     }
 
+    #[test]
+    fn grid_should_be_padded_evenly_when_centered() {
+        let grid = Grid::<u8>::from_flat(2, &[1, 2, 3, 4]).centered(4, 3);
+        assert_eq!(
+            grid,
+            Grid::from_flat(4, &[0, 1, 2, 0, 0, 3, 4, 0, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn grid_should_be_cropped_evenly_when_centered() {
+        let grid = Grid::<u8>::from_flat(3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]).centered(1, 1);
+        assert_eq!(grid, Grid::from_flat(1, &[5]));
+    }
+
+    #[test]
+    fn grid_should_keep_anchored_corner_when_resized() {
+        let grid = Grid::<u8>::from_flat(2, &[1, 2, 3, 4]);
+        assert_eq!(
+            grid.resized_anchored(3, 3, Align::End, Align::End),
+            Grid::from_flat(3, &[0, 0, 0, 0, 1, 2, 0, 3, 4])
+        );
+        assert_eq!(
+            grid.resized_anchored(3, 1, Align::End, Align::Start),
+            Grid::from_flat(3, &[3, 4, 0])
+        );
+        assert_eq!(
+            grid.resized_anchored(2, 2, Align::Start, Align::Start),
+            grid.resized(2, 2)
+        );
+    }
+
+    #[test]
+    fn grid_should_return_sub_grid() {
+        let grid = Grid::<u8>::from_flat(3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(grid.sub_grid(1..3, 0..2), Grid::from_flat(2, &[4, 5, 7, 8]));
+    }
+
+    #[test]
+    fn grid_should_drop_cells_pasted_outside() {
+        let mut grid = Grid::<u8>::new(3, 2);
+        grid.paste(&Grid::from_flat(2, &[1, 2, 3, 4]), 1, -1);
+        assert_eq!(grid, Grid::from_flat(3, &[0, 0, 0, 2, 0, 0]));
+        grid.paste(&Grid::from_flat(2, &[5, 6, 7, 8]), 0, 1);
+        assert_eq!(grid, Grid::from_flat(3, &[0, 5, 6, 2, 7, 8]));
+    }
+
+    #[test]
+    fn grid_region_should_be_filled() {
+        let mut grid = Grid::<u8>::new(3, 3);
+        grid.fill_region(0..2, 1..3, 1);
+        assert_eq!(grid, Grid::from_flat(3, &[0, 1, 1, 0, 1, 1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn grid_should_be_cropped_to_content() {
+        let grid = Grid::<u8>::from_flat(4, &[0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 2, 0]);
+        assert_eq!(grid.content_bounds(), Some((1..3, 1..3)));
+        assert_eq!(grid.cropped_to_content(), Grid::from_flat(2, &[1, 0, 0, 2]));
+    }
+
+    #[test]
+    fn grid_without_content_should_be_cropped_to_nothing() {
+        let grid = Grid::<u8>::new(3, 3);
+        assert_eq!(grid.content_bounds(), None);
+        assert_eq!(grid.cropped_to_content(), Grid::new(0, 0));
+    }
+
+    #[test]
+    fn grid_should_be_flipped() {
+        let grid = Grid::<u8>::from_flat(3, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            grid.flipped_horizontally(),
+            Grid::from_flat(3, &[3, 2, 1, 6, 5, 4])
+        );
+        assert_eq!(
+            grid.flipped_vertically(),
+            Grid::from_flat(3, &[4, 5, 6, 1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn grid_should_swap_width_and_height_when_transposed() {
+        let grid = Grid::<u8>::from_flat(3, &[1, 2, 3, 4, 5, 6]).transposed();
+        assert_eq!(grid, Grid::from_flat(2, &[1, 4, 2, 5, 3, 6]));
+    }
+
+    #[test]
+    fn grid_should_be_rotated_both_ways() {
+        let grid = Grid::<u8>::from_flat(3, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            grid.rotated_clockwise(),
+            Grid::from_flat(2, &[4, 1, 5, 2, 6, 3])
+        );
+        assert_eq!(
+            grid.rotated_counterclockwise(),
+            Grid::from_flat(2, &[3, 6, 2, 5, 1, 4])
+        );
+        assert_eq!(grid.rotated_clockwise().rotated_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn grid_should_wrap_around_when_shifted_cyclically() {
+        let grid = Grid::<u8>::from_flat(3, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            grid.shifted(0, 1, true),
+            Grid::from_flat(3, &[3, 1, 2, 6, 4, 5])
+        );
+        assert_eq!(
+            grid.shifted(-1, -1, true),
+            Grid::from_flat(3, &[5, 6, 4, 2, 3, 1])
+        );
+    }
+
+    #[test]
+    fn grid_should_drop_cells_when_shifted_non_cyclically() {
+        let grid = Grid::<u8>::from_flat(3, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            grid.shifted(0, 1, false),
+            Grid::from_flat(3, &[0, 1, 2, 0, 4, 5])
+        );
+        assert_eq!(
+            grid.shifted(1, -2, false),
+            Grid::from_flat(3, &[0, 0, 0, 3, 0, 0])
+        );
+        assert_eq!(grid.shifted(5, 0, false), Grid::new(3, 2));
+    }
+
     #[test]
     fn nullsize_grid_deserializes_correctly() {
         let grid = Grid::<u8>::from_base64("AAAAAA==").unwrap();
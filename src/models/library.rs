@@ -0,0 +1,253 @@
+//! Puzzles the player set or solved on this device
+
+/// How a puzzle came into the library
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    /// Set by the player, follows the puzzle as it is edited
+    Draft,
+    /// Opened for solving
+    Played,
+}
+
+impl EntryKind {
+    fn tag(&self) -> char {
+        match self {
+            EntryKind::Draft => 'D',
+            EntryKind::Played => 'P',
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "D" => Some(EntryKind::Draft),
+            "P" => Some(EntryKind::Played),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntryKind::Draft => "Draft",
+            EntryKind::Played => "Played",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LibraryEntry {
+    /// Tells entries apart even when they hold the same puzzle
+    pub id: u32,
+    pub name: String,
+    pub code: String,
+    pub kind: EntryKind,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Library {
+    pub entries: Vec<LibraryEntry>,
+}
+
+/// Names are stored one entry per line, with tab separated fields
+fn clean_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl Library {
+    pub fn serialize(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let tag = entry.kind.tag();
+                format!("{tag}\t{}\t{}\t{}", entry.id, entry.code, entry.name)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Entries that cannot be read are left out
+    pub fn deserialize(value: &str) -> Self {
+        let entries = value
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\t');
+                let kind = EntryKind::from_tag(fields.next()?)?;
+                let id = fields.next()?.parse().ok()?;
+                let code = fields.next()?.to_owned();
+                let name = fields.next()?.to_owned();
+                Some(LibraryEntry {
+                    id,
+                    name,
+                    code,
+                    kind,
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    fn new_id(&self) -> u32 {
+        self.entries
+            .iter()
+            .map(|entry| entry.id + 1)
+            .max()
+            .unwrap_or(1)
+    }
+
+    pub fn contains_code(&self, code: &str) -> bool {
+        self.entries.iter().any(|entry| entry.code == code)
+    }
+
+    /// List a puzzle opened for solving, returns false if it was listed already
+    pub fn add_played(&mut self, code: &str) -> bool {
+        if code.is_empty() || self.contains_code(code) {
+            return false;
+        }
+        let id = self.new_id();
+        self.entries.push(LibraryEntry {
+            id,
+            name: format!("Puzzle {id}"),
+            code: code.to_owned(),
+            kind: EntryKind::Played,
+        });
+        true
+    }
+
+    /// Follow the draft with the given id to the code after an edit, listing
+    /// the puzzle as a new draft and setting the id if there is no such draft
+    pub fn update_draft(&mut self, id: &mut Option<u32>, new_code: &str) -> bool {
+        let draft = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.kind == EntryKind::Draft && Some(entry.id) == *id);
+        match draft {
+            Some(draft) if draft.code == new_code => return false,
+            Some(draft) => draft.code = new_code.to_owned(),
+            None => {
+                let new_id = self.new_id();
+                self.entries.push(LibraryEntry {
+                    id: new_id,
+                    name: format!("Draft {new_id}"),
+                    code: new_code.to_owned(),
+                    kind: EntryKind::Draft,
+                });
+                *id = Some(new_id);
+            }
+        }
+        true
+    }
+
+    /// Returns false if the new name is blank
+    pub fn rename(&mut self, idx: usize, name: &str) -> bool {
+        let name = clean_name(name);
+        match self.entries.get_mut(idx) {
+            Some(entry) if !name.is_empty() => {
+                entry.name = name;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Copy an entry, listed right after it. A copy of a played puzzle is
+    /// played too, so it does not give away the picture
+    pub fn duplicate(&mut self, idx: usize) {
+        if let Some(entry) = self.entries.get(idx) {
+            let copy = LibraryEntry {
+                id: self.new_id(),
+                name: format!("{} (copy)", entry.name),
+                code: entry.code.clone(),
+                kind: entry.kind,
+            };
+            self.entries.insert(idx + 1, copy);
+        }
+    }
+
+    pub fn remove(&mut self, idx: usize) -> Option<LibraryEntry> {
+        (idx < self.entries.len()).then(|| self.entries.remove(idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_and_deserialize_library() {
+        let mut library = Library::default();
+        library.add_played("BQAFAA==AAAAAAAA");
+        library.update_draft(&mut None, "CgAKAA==ABAA");
+        library.rename(0, "  Cat\twith\nhat ");
+        assert_eq!(library.entries[0].name, "Cat with hat");
+        let serialized = library.serialize();
+        assert_eq!(
+            serialized,
+            "P\t1\tBQAFAA==AAAAAAAA\tCat with hat\nD\t2\tCgAKAA==ABAA\tDraft 2"
+        );
+        assert_eq!(Library::deserialize(&serialized), library);
+        assert_eq!(
+            Library::deserialize("X\t1\tcode\tname\nP\tx\tcode\tname").entries,
+            vec![]
+        );
+    }
+
+    #[test]
+    fn should_list_played_puzzles_once() {
+        let mut library = Library::default();
+        assert!(library.add_played("A"));
+        assert!(!library.add_played("A"));
+        assert!(!library.add_played(""));
+        assert_eq!(library.entries.len(), 1);
+    }
+
+    #[test]
+    fn should_follow_edited_draft() {
+        let mut library = Library::default();
+        library.add_played("A");
+        let mut draft = None;
+        // editing a played puzzle starts a new draft
+        assert!(library.update_draft(&mut draft, "B"));
+        assert!(library.update_draft(&mut draft, "C"));
+        assert!(!library.update_draft(&mut draft, "C"));
+        let codes = library.entries.iter().map(|e| (e.code.as_str(), e.kind));
+        assert_eq!(
+            codes.collect::<Vec<_>>(),
+            vec![("A", EntryKind::Played), ("C", EntryKind::Draft)]
+        );
+        assert_eq!(draft, Some(library.entries[1].id));
+    }
+
+    #[test]
+    fn should_follow_edited_duplicate_only() {
+        let mut library = Library::default();
+        let mut original = None;
+        library.update_draft(&mut original, "A");
+        library.duplicate(0);
+        let mut copy = Some(library.entries[1].id);
+        assert_ne!(copy, original);
+        assert!(library.update_draft(&mut copy, "B"));
+        assert!(library.update_draft(&mut original, "C"));
+        let codes = library.entries.iter().map(|e| e.code.as_str());
+        assert_eq!(codes.collect::<Vec<_>>(), vec!["C", "B"]);
+    }
+
+    #[test]
+    fn should_duplicate_rename_and_remove_entries() {
+        let mut library = Library::default();
+        library.add_played("A");
+        library.add_played("B");
+        library.duplicate(0);
+        assert_eq!(library.entries[1].name, "Puzzle 1 (copy)");
+        assert_eq!(library.entries[1].kind, EntryKind::Played);
+        assert_eq!(library.entries[2].code, "B");
+        assert!(!library.rename(1, " "));
+        assert_eq!(
+            library.remove(0).map(|entry| entry.code),
+            Some("A".to_owned())
+        );
+        assert!(library.contains_code("A"));
+        assert_eq!(library.remove(5), None);
+        // names follow the ids, so they do not repeat after a removal
+        library.add_played("C");
+        assert_eq!(library.entries[2].name, "Puzzle 4");
+    }
+}
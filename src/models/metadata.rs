@@ -0,0 +1,102 @@
+//! Details about a puzzle that travel along with its code
+
+/// Separates the fields before encoding, not expected in any of them
+const FIELD_SEPARATOR: char = '\u{1f}';
+const HIDE_TITLE_FLAG: &str = "h";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub title: String,
+    pub author: String,
+    pub description: String,
+    /// Day the puzzle was set, as YYYY-MM-DD
+    pub created: String,
+    /// Keep the title from spoiling the picture until the puzzle is solved
+    pub hide_title: bool,
+}
+
+impl Metadata {
+    /// Nothing worth putting into the puzzle code
+    pub fn is_empty(&self) -> bool {
+        self.title.is_empty()
+            && self.author.is_empty()
+            && self.description.is_empty()
+            && self.created.is_empty()
+    }
+
+    /// URL-safe base64 of the fields
+    pub fn serialize(&self) -> String {
+        let flags = if self.hide_title { HIDE_TITLE_FLAG } else { "" };
+        let fields = [
+            self.title.as_str(),
+            &self.author,
+            &self.description,
+            &self.created,
+            flags,
+        ]
+        .map(|field| field.replace(FIELD_SEPARATOR, ""));
+        base64::encode_config(
+            fields.join(&FIELD_SEPARATOR.to_string()),
+            base64::URL_SAFE_NO_PAD,
+        )
+    }
+
+    pub fn deserialize(code: &str) -> Option<Self> {
+        let bytes = base64::decode_config(code, base64::URL_SAFE_NO_PAD).ok()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let mut fields = text.split(FIELD_SEPARATOR).map(str::to_owned);
+        Some(Self {
+            title: fields.next()?,
+            author: fields.next()?,
+            description: fields.next()?,
+            created: fields.next()?,
+            hide_title: fields.next()?.contains(HIDE_TITLE_FLAG),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_and_deserialize_metadata() {
+        let metadata = Metadata {
+            title: "Schöne Katze".to_owned(),
+            author: "mh".to_owned(),
+            description: "A cat.\nSitting.".to_owned(),
+            created: "2023-01-15".to_owned(),
+            hide_title: true,
+        };
+        let code = metadata.serialize();
+        assert!(code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(Metadata::deserialize(&code), Some(metadata));
+        assert_eq!(
+            Metadata::deserialize(&Metadata::default().serialize()),
+            Some(Metadata::default())
+        );
+    }
+
+    #[test]
+    fn should_reject_malformed_metadata() {
+        assert_eq!(Metadata::deserialize("not base64!"), None);
+        let too_few_fields = base64::encode_config("title", base64::URL_SAFE_NO_PAD);
+        assert_eq!(Metadata::deserialize(&too_few_fields), None);
+    }
+
+    #[test]
+    fn should_be_empty_without_text() {
+        let hidden_nothing = Metadata {
+            hide_title: true,
+            ..Metadata::default()
+        };
+        assert!(hidden_nothing.is_empty());
+        let titled = Metadata {
+            title: "Cat".to_owned(),
+            ..Metadata::default()
+        };
+        assert!(!titled.is_empty());
+    }
+}
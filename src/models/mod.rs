@@ -1,2 +1,14 @@
 pub mod grid;
 pub mod board;
+pub mod ascii_art;
+pub mod bitmap;
+pub mod font;
+pub mod clues;
+pub mod solver;
+pub mod shapes;
+pub mod symmetry;
+pub mod uniqueness;
+pub mod explanation;
+pub mod records;
+pub mod library;
+pub mod metadata;
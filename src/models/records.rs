@@ -0,0 +1,94 @@
+//! Effort spent on solving a puzzle
+
+/// Time, moves and mistakes of one attempt, or the bests over several
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Record {
+    /// Time spent actively solving, in milliseconds
+    pub time_ms: u64,
+    pub moves: u32,
+    pub mistakes: u32,
+}
+
+impl Record {
+    pub fn serialize(&self) -> String {
+        format!("{},{},{}", self.time_ms, self.moves, self.mistakes)
+    }
+
+    pub fn deserialize(value: &str) -> Option<Self> {
+        let mut parts = value.split(',');
+        let record = Self {
+            time_ms: parts.next()?.parse().ok()?,
+            moves: parts.next()?.parse().ok()?,
+            mistakes: parts.next()?.parse().ok()?,
+        };
+        parts.next().is_none().then_some(record)
+    }
+
+    /// Best of each measure, which need not come from the same attempt
+    pub fn best_with(&self, other: &Self) -> Self {
+        Self {
+            time_ms: self.time_ms.min(other.time_ms),
+            moves: self.moves.min(other.moves),
+            mistakes: self.mistakes.min(other.mistakes),
+        }
+    }
+}
+
+/// Duration as m:ss, or h:mm:ss from an hour on
+pub fn format_duration(ms: u64) -> String {
+    let seconds = ms / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_and_deserialize_record() {
+        let record = Record {
+            time_ms: 83_250,
+            moves: 42,
+            mistakes: 3,
+        };
+        assert_eq!(record.serialize(), "83250,42,3");
+        assert_eq!(Record::deserialize(&record.serialize()), Some(record));
+        assert_eq!(Record::deserialize("83250,42"), None);
+        assert_eq!(Record::deserialize("83250,42,3,1"), None);
+        assert_eq!(Record::deserialize("a,b,c"), None);
+    }
+
+    #[test]
+    fn should_combine_best_of_each_measure() {
+        let fast = Record {
+            time_ms: 1000,
+            moves: 30,
+            mistakes: 2,
+        };
+        let careful = Record {
+            time_ms: 5000,
+            moves: 20,
+            mistakes: 0,
+        };
+        let best = Record {
+            time_ms: 1000,
+            moves: 20,
+            mistakes: 0,
+        };
+        assert_eq!(fast.best_with(&careful), best);
+        assert_eq!(careful.best_with(&fast), best);
+    }
+
+    #[test]
+    fn should_format_duration() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(59_999), "0:59");
+        assert_eq!(format_duration(83_250), "1:23");
+        assert_eq!(format_duration(3_723_000), "1:02:03");
+    }
+}
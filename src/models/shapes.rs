@@ -0,0 +1,386 @@
+//! Iterators over the (row, col) coordinates of cells covered by drawing tools
+
+use super::board::FieldCell;
+use super::grid::Grid;
+
+/// Iterator over the cells of a straight line between two cells (Bresenham)
+pub struct StraightLine {
+    current: (i32, i32),
+    end: (i32, i32),
+    delta: (i32, i32),
+    step: (i32, i32),
+    error: i32,
+    done: bool,
+}
+
+impl StraightLine {
+    pub fn new(start: (i32, i32), end: (i32, i32)) -> Self {
+        let delta = ((end.0 - start.0).abs(), -(end.1 - start.1).abs());
+        Self {
+            current: start,
+            end,
+            delta,
+            step: ((end.0 - start.0).signum(), (end.1 - start.1).signum()),
+            error: delta.0 + delta.1,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for StraightLine {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.current;
+        if self.current == self.end {
+            self.done = true;
+            return Some(result);
+        }
+        let doubled_error = 2 * self.error;
+        if doubled_error >= self.delta.1 {
+            self.error += self.delta.1;
+            self.current.0 += self.step.0;
+        }
+        if doubled_error <= self.delta.0 {
+            self.error += self.delta.0;
+            self.current.1 += self.step.1;
+        }
+        Some(result)
+    }
+}
+
+/// Iterator over the cells along a path of cells, gaps between consecutive
+/// cells are bridged with straight lines
+pub struct Path {
+    points: Vec<(i32, i32)>,
+    next_point: usize,
+    segment: Option<StraightLine>,
+}
+
+impl Path {
+    pub fn new(points: Vec<(i32, i32)>) -> Self {
+        Self {
+            points,
+            next_point: 0,
+            segment: None,
+        }
+    }
+}
+
+impl Iterator for Path {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cell) = self.segment.as_mut().and_then(|segment| segment.next()) {
+                return Some(cell);
+            }
+            let point = *self.points.get(self.next_point)?;
+            self.segment = Some(if self.next_point == 0 {
+                StraightLine::new(point, point)
+            } else {
+                // skip the first cell, it ended the previous segment
+                let mut segment = StraightLine::new(self.points[self.next_point - 1], point);
+                segment.next();
+                segment
+            });
+            self.next_point += 1;
+        }
+    }
+}
+
+/// Iterator over the border cells of a rectangle, row by row
+pub struct RectOutline {
+    start: (i32, i32),
+    end: (i32, i32),
+    current: (i32, i32),
+}
+
+impl RectOutline {
+    pub fn new(corner1: (i32, i32), corner2: (i32, i32)) -> Self {
+        let start = (corner1.0.min(corner2.0), corner1.1.min(corner2.1));
+        let end = (corner1.0.max(corner2.0), corner1.1.max(corner2.1));
+        Self {
+            start,
+            end,
+            current: start,
+        }
+    }
+}
+
+impl Iterator for RectOutline {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.0 > self.end.0 {
+            return None;
+        }
+        let result = self.current;
+        let on_horizontal_edge = self.current.0 == self.start.0 || self.current.0 == self.end.0;
+        if self.current.1 < self.end.1 && on_horizontal_edge {
+            self.current.1 += 1;
+        } else if self.current.1 == self.start.1 && self.end.1 > self.start.1 {
+            self.current.1 = self.end.1;
+        } else {
+            self.current = (self.current.0 + 1, self.start.1);
+        }
+        Some(result)
+    }
+}
+
+/// Cells on the outline of the ellipse inscribed in the rectangle spanned by
+/// two corners, row by row
+pub struct Ellipse {
+    cells: std::vec::IntoIter<(i32, i32)>,
+}
+
+impl Ellipse {
+    pub fn new(corner1: (i32, i32), corner2: (i32, i32)) -> Self {
+        let start = (corner1.0.min(corner2.0), corner1.1.min(corner2.1));
+        let end = (corner1.0.max(corner2.0), corner1.1.max(corner2.1));
+        let center = (
+            (start.0 + end.0) as f64 / 2.0,
+            (start.1 + end.1) as f64 / 2.0,
+        );
+        let radius = (
+            (end.0 - start.0) as f64 / 2.0 + 0.5,
+            (end.1 - start.1) as f64 / 2.0 + 0.5,
+        );
+        let inside = |(row, col): (i32, i32)| {
+            let dy = (row as f64 - center.0) / radius.0;
+            let dx = (col as f64 - center.1) / radius.1;
+            dx * dx + dy * dy <= 1.0
+        };
+        let mut cells = vec![];
+        for row in start.0..=end.0 {
+            for col in start.1..=end.1 {
+                let neighbours = [
+                    (row - 1, col),
+                    (row + 1, col),
+                    (row, col - 1),
+                    (row, col + 1),
+                ];
+                if inside((row, col)) && !neighbours.into_iter().all(inside) {
+                    cells.push((row, col));
+                }
+            }
+        }
+        Self {
+            cells: cells.into_iter(),
+        }
+    }
+}
+
+impl Iterator for Ellipse {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cells.next()
+    }
+}
+
+/// Iterator over the cells connected horizontally or vertically to a start
+/// cell through cells of the same state, in no particular order
+pub struct FloodFill<'a> {
+    grid: &'a Grid<FieldCell>,
+    state: FieldCell,
+    visited: Grid<bool>,
+    pending: Vec<(i32, i32)>,
+}
+
+impl<'a> FloodFill<'a> {
+    pub fn new(grid: &'a Grid<FieldCell>, start: (i32, i32)) -> Self {
+        let in_grid = start.0 >= 0
+            && start.1 >= 0
+            && (start.0 as usize) < grid.height()
+            && (start.1 as usize) < grid.width();
+        let mut visited = Grid::new(grid.width(), grid.height());
+        let (state, pending) = if in_grid {
+            visited[start.0 as usize][start.1 as usize] = true;
+            (grid[start.0 as usize][start.1 as usize], vec![start])
+        } else {
+            (FieldCell::Empty, vec![])
+        };
+        Self {
+            grid,
+            state,
+            visited,
+            pending,
+        }
+    }
+}
+
+impl<'a> Iterator for FloodFill<'a> {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (row, col) = self.pending.pop()?;
+        for (nrow, ncol) in [
+            (row - 1, col),
+            (row + 1, col),
+            (row, col - 1),
+            (row, col + 1),
+        ] {
+            if nrow < 0 || ncol < 0 {
+                continue;
+            }
+            let (nrow_u, ncol_u) = (nrow as usize, ncol as usize);
+            if nrow_u >= self.grid.height()
+                || ncol_u >= self.grid.width()
+                || self.visited[nrow_u][ncol_u]
+                || self.grid[nrow_u][ncol_u] != self.state
+            {
+                continue;
+            }
+            self.visited[nrow_u][ncol_u] = true;
+            self.pending.push((nrow, ncol));
+        }
+        Some((row, col))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FieldCell::{Empty as E, Filled as F};
+
+    #[test]
+    fn line_should_walk_over_single_cell() {
+        let mut line = StraightLine::new((2, 3), (2, 3));
+        assert_eq!(Some((2, 3)), line.next());
+        assert_eq!(None, line.next());
+    }
+
+    #[test]
+    fn line_should_walk_over_cells_in_a_row_in_both_directions() {
+        let line = StraightLine::new((1, 1), (1, 4)).collect::<Vec<_>>();
+        assert_eq!(line, vec![(1, 1), (1, 2), (1, 3), (1, 4)]);
+        let line = StraightLine::new((1, 4), (1, 1)).collect::<Vec<_>>();
+        assert_eq!(line, vec![(1, 4), (1, 3), (1, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn line_should_walk_over_diagonal() {
+        let line = StraightLine::new((0, 0), (3, 3)).collect::<Vec<_>>();
+        assert_eq!(line, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn line_should_walk_over_shallow_slope() {
+        let line = StraightLine::new((0, 0), (2, 5)).collect::<Vec<_>>();
+        assert_eq!(line.len(), 6);
+        assert_eq!(line.first(), Some(&(0, 0)));
+        assert_eq!(line.last(), Some(&(2, 5)));
+        for pair in line.windows(2) {
+            assert_eq!(pair[1].1, pair[0].1 + 1);
+            assert!(pair[1].0 - pair[0].0 <= 1);
+        }
+    }
+
+    #[test]
+    fn path_should_walk_over_single_point() {
+        assert_eq!(Path::new(vec![(4, 4)]).collect::<Vec<_>>(), vec![(4, 4)]);
+        assert_eq!(Path::new(vec![]).next(), None);
+    }
+
+    #[test]
+    fn path_should_bridge_gaps_without_repeating_points() {
+        let path = Path::new(vec![(0, 0), (0, 2), (2, 2)]).collect::<Vec<_>>();
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn rect_outline_should_walk_over_border_row_by_row() {
+        let outline = RectOutline::new((2, 3), (0, 0)).collect::<Vec<_>>();
+        assert_eq!(
+            outline,
+            vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (1, 0),
+                (1, 3),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn rect_outline_should_walk_over_thin_rectangles() {
+        assert_eq!(
+            RectOutline::new((1, 1), (1, 1)).collect::<Vec<_>>(),
+            vec![(1, 1)]
+        );
+        assert_eq!(
+            RectOutline::new((0, 0), (2, 0)).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0)]
+        );
+        assert_eq!(
+            RectOutline::new((0, 0), (0, 2)).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn ellipse_should_cover_small_boxes_completely() {
+        assert_eq!(
+            Ellipse::new((0, 0), (0, 0)).collect::<Vec<_>>(),
+            vec![(0, 0)]
+        );
+        assert_eq!(
+            Ellipse::new((0, 0), (0, 2)).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn ellipse_should_leave_corners_and_centre_empty() {
+        let ellipse = Ellipse::new((0, 0), (6, 6)).collect::<Vec<_>>();
+        assert!(!ellipse.contains(&(0, 0)));
+        assert!(!ellipse.contains(&(6, 6)));
+        assert!(!ellipse.contains(&(3, 3)));
+        for extreme in [(0, 3), (6, 3), (3, 0), (3, 6)] {
+            assert!(ellipse.contains(&extreme), "{extreme:?} missing");
+        }
+    }
+
+    #[test]
+    fn ellipse_should_be_symmetric() {
+        let ellipse = Ellipse::new((0, 0), (4, 8)).collect::<Vec<_>>();
+        for &(row, col) in ellipse.iter() {
+            assert!(ellipse.contains(&(4 - row, col)));
+            assert!(ellipse.contains(&(row, 8 - col)));
+        }
+    }
+
+    #[test]
+    fn flood_fill_should_stop_at_different_cells() {
+        let grid = Grid::from_flat(3, &[E, F, E, E, F, E, E, F, F]);
+        let mut filled = FloodFill::new(&grid, (0, 0)).collect::<Vec<_>>();
+        filled.sort();
+        assert_eq!(filled, vec![(0, 0), (1, 0), (2, 0)]);
+        let mut filled = FloodFill::new(&grid, (2, 2)).collect::<Vec<_>>();
+        filled.sort();
+        assert_eq!(filled, vec![(0, 1), (1, 1), (2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn flood_fill_should_not_leak_diagonally() {
+        let grid = Grid::from_flat(2, &[E, F, F, E]);
+        assert_eq!(FloodFill::new(&grid, (0, 0)).count(), 1);
+    }
+
+    #[test]
+    fn flood_fill_should_ignore_start_outside_of_grid() {
+        let grid = Grid::<FieldCell>::new(2, 2);
+        assert_eq!(FloodFill::new(&grid, (-1, 0)).count(), 0);
+        assert_eq!(FloodFill::new(&grid, (0, 2)).count(), 0);
+    }
+}
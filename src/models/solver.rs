@@ -0,0 +1,514 @@
+//! Nonogram solving on grids of `FieldCell`s, where `Empty` stands for a cell
+//! that is not known yet and `Marked` for a cell known to be empty, just like
+//! on the player's field
+
+use super::board::FieldCell;
+use super::clues::{Clues, Line};
+use super::grid::Grid;
+
+/// The known cells cannot be completed to match the clues
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contradiction(pub Option<Line>);
+
+/// Whether run `j` of the clue can start at cell `start` of the line
+fn can_place(clue: &[u8], line: &[FieldCell], start: usize, j: usize) -> bool {
+    let (n, len) = (line.len(), clue[j] as usize);
+    start + len <= n
+        && line[start..start + len]
+            .iter()
+            .all(|&cell| cell != FieldCell::Marked)
+        && (start + len == n || line[start + len] != FieldCell::Filled)
+}
+
+/// Next position after placing run `j` at `start`, skipping the gap
+fn after_run(clue: &[u8], n: usize, start: usize, j: usize) -> usize {
+    (start + clue[j] as usize + 1).min(n)
+}
+
+/// fits[i][j]: cells i.. can hold runs j.., given cell i may start a run
+fn fit_table(clue: &[u8], line: &[FieldCell]) -> Vec<Vec<bool>> {
+    let (n, k) = (line.len(), clue.len());
+    let mut fits = vec![vec![false; k + 1]; n + 1];
+    fits[n][k] = true;
+    for i in (0..n).rev() {
+        for j in 0..=k {
+            let skip = line[i] != FieldCell::Filled && fits[i + 1][j];
+            let place =
+                j < k && can_place(clue, line, i, j) && fits[after_run(clue, n, i, j)][j + 1];
+            fits[i][j] = skip || place;
+        }
+    }
+    fits
+}
+
+/// Start of every run in the placement consistent with the known cells that
+/// puts each run as far left as possible, None if there is no such placement
+pub fn leftmost_placement(clue: &[u8], line: &[FieldCell]) -> Option<Vec<usize>> {
+    let fits = fit_table(clue, line);
+    if !fits[0][0] {
+        return None;
+    }
+    let (n, k) = (line.len(), clue.len());
+    let (mut i, mut starts) = (0, vec![]);
+    while starts.len() < k {
+        let j = starts.len();
+        if can_place(clue, line, i, j) && fits[after_run(clue, n, i, j)][j + 1] {
+            starts.push(i);
+            i = after_run(clue, n, i, j);
+        } else {
+            i += 1;
+        }
+    }
+    Some(starts)
+}
+
+/// Like `leftmost_placement`, with each run as far right as possible
+pub fn rightmost_placement(clue: &[u8], line: &[FieldCell]) -> Option<Vec<usize>> {
+    let reversed_clue = clue.iter().rev().copied().collect::<Vec<_>>();
+    let reversed_line = line.iter().rev().copied().collect::<Vec<_>>();
+    let starts = leftmost_placement(&reversed_clue, &reversed_line)?;
+    Some(
+        starts
+            .iter()
+            .rev()
+            .zip(clue.iter())
+            .map(|(&start, &len)| line.len() - start - len as usize)
+            .collect(),
+    )
+}
+
+/// Deduce all cells of a line that are the same in every placement of the clue
+/// consistent with the known cells, returns None if there is no such placement
+pub fn solve_line(clue: &[u8], line: &[FieldCell]) -> Option<Vec<FieldCell>> {
+    let (n, k) = (line.len(), clue.len());
+    let fits = fit_table(clue, line);
+    if !fits[0][0] {
+        return None;
+    }
+    let mut reachable = vec![vec![false; k + 1]; n + 1];
+    reachable[0][0] = true;
+    let (mut maybe_filled, mut maybe_empty) = (vec![false; n], vec![false; n]);
+    for i in 0..n {
+        for j in 0..=k {
+            if !reachable[i][j] || !fits[i][j] {
+                continue;
+            }
+            if line[i] != FieldCell::Filled && fits[i + 1][j] {
+                maybe_empty[i] = true;
+                reachable[i + 1][j] = true;
+            }
+            if j < k && can_place(clue, line, i, j) && fits[after_run(clue, n, i, j)][j + 1] {
+                let end = i + clue[j] as usize;
+                maybe_filled[i..end]
+                    .iter_mut()
+                    .for_each(|cell| *cell = true);
+                if end < n {
+                    maybe_empty[end] = true;
+                }
+                reachable[after_run(clue, n, i, j)][j + 1] = true;
+            }
+        }
+    }
+    Some(
+        maybe_filled
+            .iter()
+            .zip(maybe_empty.iter())
+            .map(|(&filled, &empty)| match (filled, empty) {
+                (true, false) => FieldCell::Filled,
+                (false, true) => FieldCell::Marked,
+                _ => FieldCell::Empty,
+            })
+            .collect(),
+    )
+}
+
+pub fn line_cells(grid: &Grid<FieldCell>, line: Line) -> Vec<FieldCell> {
+    match line {
+        Line::Row(row) => grid[row].to_vec(),
+        Line::Col(col) => (0..grid.height()).map(|row| grid[row][col]).collect(),
+    }
+}
+
+/// Position on the grid of the `idx`th cell of a line, as (row, col)
+pub fn line_position(line: Line, idx: usize) -> (usize, usize) {
+    match line {
+        Line::Row(row) => (row, idx),
+        Line::Col(col) => (idx, col),
+    }
+}
+
+/// Apply the line solver to a single line, returns the newly deduced cells as
+/// (row, col, state)
+pub fn deduce_line(
+    clues: &Clues,
+    grid: &mut Grid<FieldCell>,
+    line: Line,
+) -> Result<Vec<(usize, usize, FieldCell)>, Contradiction> {
+    let cells = line_cells(grid, line);
+    let solved = solve_line(clues.line(line), &cells).ok_or(Contradiction(Some(line)))?;
+    let mut deduced = vec![];
+    for (idx, (&old, &new)) in cells.iter().zip(solved.iter()).enumerate() {
+        if old == FieldCell::Empty && new != FieldCell::Empty {
+            let (row, col) = line_position(line, idx);
+            grid[row][col] = new;
+            deduced.push((row, col, new));
+        }
+    }
+    Ok(deduced)
+}
+
+/// Lines whose known cells cannot be completed to match their clue, looking at
+/// each line on its own
+pub fn contradicting_lines(clues: &Clues, grid: &Grid<FieldCell>) -> Vec<Line> {
+    clues
+        .lines()
+        .filter(|&line| solve_line(clues.line(line), &line_cells(grid, line)).is_none())
+        .collect()
+}
+
+/// A line and the cells deduced in it, as (row, col, state)
+pub type LineDeduction = (Line, Vec<(usize, usize, FieldCell)>);
+
+/// The first line after `after`, going through all rows and then all columns
+/// and starting over, from which the line solver deduces new cells, None if no
+/// line yields anything
+pub fn next_deduction(
+    clues: &Clues,
+    grid: &Grid<FieldCell>,
+    after: Option<Line>,
+) -> Result<Option<LineDeduction>, Contradiction> {
+    let lines = clues.lines().collect::<Vec<_>>();
+    let start = after
+        .and_then(|after| lines.iter().position(|&line| line == after))
+        .map_or(0, |idx| idx + 1);
+    // deduce_line only changes the grid when it returns new cells
+    let mut grid = grid.clone();
+    for i in 0..lines.len() {
+        let line = lines[(start + i) % lines.len()];
+        let deduced = deduce_line(clues, &mut grid, line)?;
+        if !deduced.is_empty() {
+            return Ok(Some((line, deduced)));
+        }
+    }
+    Ok(None)
+}
+
+/// Apply the line solver until nothing more can be deduced, solving at most
+/// `budget` lines, returns false if the budget ran out first. Only lines crossing a cell deduced since they were
+/// last solved are solved again, starting with the lines through `changed` if
+/// given, otherwise with all lines
+pub fn propagate_within(
+    clues: &Clues,
+    grid: &mut Grid<FieldCell>,
+    changed: Option<(usize, usize)>,
+    budget: &mut usize,
+) -> Result<bool, Contradiction> {
+    let mut dirty_rows = vec![changed.is_none(); clues.height()];
+    let mut dirty_cols = vec![changed.is_none(); clues.width()];
+    if let Some((row, col)) = changed {
+        dirty_rows[row] = true;
+        dirty_cols[col] = true;
+    }
+    loop {
+        let mut deduced_any = false;
+        for line in clues.lines() {
+            let dirty = match line {
+                Line::Row(row) => &mut dirty_rows[row],
+                Line::Col(col) => &mut dirty_cols[col],
+            };
+            if !std::mem::take(dirty) {
+                continue;
+            }
+            if *budget == 0 {
+                return Ok(false);
+            }
+            *budget -= 1;
+            for (row, col, _) in deduce_line(clues, grid, line)? {
+                match line {
+                    Line::Row(_) => dirty_cols[col] = true,
+                    Line::Col(_) => dirty_rows[row] = true,
+                }
+                deduced_any = true;
+            }
+        }
+        if !deduced_any {
+            return Ok(true);
+        }
+    }
+}
+
+/// Number of the pass over all lines, counted from 1, in which the line solver
+/// deduces each cell, None for cells it cannot determine
+pub fn deduction_passes(clues: &Clues) -> Result<Grid<Option<usize>>, Contradiction> {
+    let mut grid = Grid::new(clues.width(), clues.height());
+    let mut passes = Grid::new(clues.width(), clues.height());
+    for pass in 1.. {
+        let mut changed = false;
+        for line in clues.lines() {
+            for (row, col, _) in deduce_line(clues, &mut grid, line)? {
+                passes[row][col] = Some(pass);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    Ok(passes)
+}
+
+/// Line solves a search may spend before it gives up. A line solve on a 25
+/// wide board takes about 12 µs in a release build, so a search stays around
+/// a tenth of a second even in the browser
+pub const SEARCH_BUDGET: usize = 4_000;
+
+/// Solutions found by a search, which may have given up before finding all
+/// of those asked for
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Solutions {
+    pub found: Vec<Grid<FieldCell>>,
+    pub gave_up: bool,
+}
+
+impl Solutions {
+    /// Whether exactly one solution exists, None if the search gave up before
+    /// this was settled
+    pub fn unique(&self) -> Option<bool> {
+        match self.found.len() {
+            0 | 1 if self.gave_up => None,
+            count => Some(count == 1),
+        }
+    }
+}
+
+/// Returns false once the budget of line solves ran out
+fn search(
+    clues: &Clues,
+    mut grid: Grid<FieldCell>,
+    guessed: Option<(usize, usize)>,
+    limit: usize,
+    budget: &mut usize,
+    found: &mut Vec<Grid<FieldCell>>,
+) -> bool {
+    if found.len() >= limit {
+        return true;
+    }
+    match propagate_within(clues, &mut grid, guessed, budget) {
+        Ok(true) => (),
+        Ok(false) => return false,
+        Err(_) => return true,
+    }
+    let unknown = (0..grid.height())
+        .flat_map(|row| (0..grid.width()).map(move |col| (row, col)))
+        .find(|&(row, col)| grid[row][col] == FieldCell::Empty);
+    match unknown {
+        None => found.push(grid),
+        Some((row, col)) => {
+            for guess in [FieldCell::Filled, FieldCell::Marked] {
+                let mut guessed = grid.clone();
+                guessed[row][col] = guess;
+                if !search(clues, guessed, Some((row, col)), limit, budget, found) {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+/// Like `solutions_from`, spending line solves from a budget that may be
+/// shared by several searches
+pub fn solutions_within(
+    clues: &Clues,
+    start: &Grid<FieldCell>,
+    limit: usize,
+    budget: &mut usize,
+) -> Solutions {
+    let mut found = vec![];
+    let gave_up = !search(clues, start.clone(), None, limit, budget, &mut found);
+    for solution in found.iter_mut() {
+        for row in 0..solution.height() {
+            for cell in solution[row].iter_mut() {
+                if *cell == FieldCell::Marked {
+                    *cell = FieldCell::Empty;
+                }
+            }
+        }
+    }
+    Solutions { found, gave_up }
+}
+
+/// Find up to `limit` solutions extending the known cells of `start`, solutions
+/// contain only `Filled` and `Empty` cells
+pub fn solutions_from(clues: &Clues, start: &Grid<FieldCell>, limit: usize) -> Solutions {
+    let mut budget = SEARCH_BUDGET;
+    solutions_within(clues, start, limit, &mut budget)
+}
+
+/// Find up to `limit` solutions of the clues
+pub fn solutions(clues: &Clues, limit: usize) -> Solutions {
+    solutions_from(clues, &Grid::new(clues.width(), clues.height()), limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FieldCell::{Empty as U, Filled as F, Marked as X};
+
+    #[test]
+    fn should_fill_overlap_of_long_run() {
+        assert_eq!(solve_line(&[4], &[U; 5]), Some(vec![U, F, F, F, U]));
+    }
+
+    #[test]
+    fn should_solve_exactly_fitting_clue() {
+        assert_eq!(solve_line(&[2, 2], &[U; 5]), Some(vec![F, F, X, F, F]));
+    }
+
+    #[test]
+    fn should_mark_line_without_runs() {
+        assert_eq!(solve_line(&[], &[U; 3]), Some(vec![X, X, X]));
+    }
+
+    #[test]
+    fn should_deduce_nothing_from_short_run() {
+        assert_eq!(solve_line(&[1], &[U; 3]), Some(vec![U, U, U]));
+    }
+
+    #[test]
+    fn should_use_known_cells() {
+        assert_eq!(solve_line(&[1], &[U, F, U]), Some(vec![X, F, X]));
+        assert_eq!(solve_line(&[2], &[X, U, U, U]), Some(vec![X, U, F, U]));
+        assert_eq!(
+            solve_line(&[3], &[U, U, X, U, U, U]),
+            Some(vec![X, X, X, F, F, F])
+        );
+    }
+
+    #[test]
+    fn should_detect_contradicting_line() {
+        assert_eq!(solve_line(&[2], &[U, X, U]), None);
+        assert_eq!(solve_line(&[1], &[F, X, F]), None);
+        assert_eq!(solve_line(&[], &[U, F]), None);
+        assert_eq!(solve_line(&[3], &[U, U]), None);
+    }
+
+    #[test]
+    fn should_find_extreme_placements() {
+        let line = [U, U, X, U, U, U, U];
+        assert_eq!(leftmost_placement(&[1, 2], &line), Some(vec![0, 3]));
+        assert_eq!(rightmost_placement(&[1, 2], &line), Some(vec![3, 5]));
+        assert_eq!(leftmost_placement(&[2], &[U, U, F, U]), Some(vec![1]));
+        assert_eq!(rightmost_placement(&[2], &[U, U, F, U]), Some(vec![2]));
+        assert_eq!(leftmost_placement(&[], &[U, U]), Some(vec![]));
+        assert_eq!(rightmost_placement(&[3], &[U, U]), None);
+    }
+
+    #[test]
+    fn should_find_lines_contradicting_their_clue() {
+        let picture = Grid::from_flat(3, &[F, F, F, U, F, U, U, F, U]);
+        let clues = Clues::from_solution(&picture);
+        assert!(contradicting_lines(&clues, &Grid::new(3, 3)).is_empty());
+        // both fills fit the row, but not the column
+        let grid = Grid::from_flat(3, &[U, U, U, F, U, U, F, U, U]);
+        assert_eq!(contradicting_lines(&clues, &grid), vec![Line::Col(0)]);
+        let grid = Grid::from_flat(3, &[U, X, U, U, U, U, U, U, U]);
+        assert_eq!(
+            contradicting_lines(&clues, &grid),
+            vec![Line::Row(0), Line::Col(1)]
+        );
+    }
+
+    #[test]
+    fn should_count_passes_needed_per_cell() {
+        let solution = Grid::from_flat(3, &[F, F, U, U, F, U, U, F, F]);
+        let passes = deduction_passes(&Clues::from_solution(&solution)).unwrap();
+        // the middle column with clue 3 is filled in the first pass, the empty
+        // ends of the middle row only follow from it in the second
+        assert_eq!(passes[0][1], Some(1));
+        assert_eq!(passes[1][1], Some(1));
+        assert_eq!(passes[1][0], Some(2));
+        assert_eq!(passes[1][2], Some(2));
+        // the picture could be mirrored, so the corners stay unknown
+        assert_eq!(passes[0][0], None);
+        assert_eq!(passes[2][2], None);
+    }
+
+    #[test]
+    fn should_find_next_line_with_deductions() {
+        let picture = Grid::from_flat(3, &[F, F, F, U, F, U, U, F, U]);
+        let (clues, grid) = (Clues::from_solution(&picture), Grid::new(3, 3));
+        let (line, deduced) = next_deduction(&clues, &grid, None).unwrap().unwrap();
+        assert_eq!(line, Line::Row(0));
+        assert_eq!(deduced, vec![(0, 0, F), (0, 1, F), (0, 2, F)]);
+        let grid = Grid::from_flat(3, &[F, F, F, U, U, U, U, U, U]);
+        let (line, deduced) = next_deduction(&clues, &grid, Some(line)).unwrap().unwrap();
+        assert_eq!(line, Line::Col(0));
+        assert_eq!(deduced, vec![(1, 0, X), (2, 0, X)]);
+        let solved = Grid::from_flat(3, &[F, F, F, X, F, X, X, F, X]);
+        assert_eq!(next_deduction(&clues, &solved, Some(line)), Ok(None));
+    }
+
+    #[test]
+    fn should_solve_unique_puzzle() {
+        let solution = Grid::from_flat(3, &[F, U, F, F, F, F, U, F, U]);
+        let clues = Clues::from_solution(&solution);
+        let found = solutions(&clues, 2);
+        assert_eq!(found.found, vec![solution]);
+        assert_eq!(found.unique(), Some(true));
+    }
+
+    #[test]
+    fn should_find_all_solutions_of_ambiguous_puzzle() {
+        let clues = Clues {
+            rows: vec![vec![1], vec![1]],
+            cols: vec![vec![1], vec![1]],
+        };
+        let found = solutions(&clues, 10).found;
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&Grid::from_flat(2, &[F, U, U, F])));
+        assert!(found.contains(&Grid::from_flat(2, &[U, F, F, U])));
+        assert_eq!(solutions(&clues, 1).found.len(), 1);
+        assert_eq!(solutions(&clues, 2).unique(), Some(false));
+    }
+
+    #[test]
+    fn should_find_no_solution_for_contradicting_clues() {
+        let clues = Clues {
+            rows: vec![vec![2], vec![]],
+            cols: vec![vec![], vec![1]],
+        };
+        assert_eq!(solutions(&clues, 2), Solutions::default());
+    }
+
+    #[test]
+    fn should_respect_known_cells() {
+        let clues = Clues {
+            rows: vec![vec![1], vec![1]],
+            cols: vec![vec![1], vec![1]],
+        };
+        let start = Grid::from_flat(2, &[X, U, U, U]);
+        assert_eq!(
+            solutions_from(&clues, &start, 10).found,
+            vec![Grid::from_flat(2, &[U, F, F, U])]
+        );
+    }
+
+    #[test]
+    fn should_give_up_when_budget_runs_out() {
+        let clues = Clues {
+            rows: vec![vec![1], vec![1]],
+            cols: vec![vec![1], vec![1]],
+        };
+        let start = Grid::new(2, 2);
+        let found = solutions_within(&clues, &start, 2, &mut 4);
+        assert!(found.gave_up);
+        assert_eq!(found.unique(), None);
+        let mut budget = 100;
+        assert_eq!(
+            solutions_within(&clues, &start, 2, &mut budget).unique(),
+            Some(false)
+        );
+        assert!(budget < 100);
+    }
+}
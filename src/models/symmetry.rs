@@ -0,0 +1,111 @@
+use itertools::Itertools;
+
+/// Mirroring applied while drawing, so that every set cell is also set at its
+/// symmetric positions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    None,
+    /// Mirrored left to right along a vertical axis
+    Vertical,
+    /// Mirrored top to bottom along a horizontal axis
+    Horizontal,
+    /// Mirrored along both axes
+    Both,
+    /// Rotated by 180° around the centre
+    Rotational,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 5] = [
+        Symmetry::None,
+        Symmetry::Vertical,
+        Symmetry::Horizontal,
+        Symmetry::Both,
+        Symmetry::Rotational,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Symmetry::None => "Off",
+            Symmetry::Vertical => "Vertical axis",
+            Symmetry::Horizontal => "Horizontal axis",
+            Symmetry::Both => "Both axes",
+            Symmetry::Rotational => "Rotational (180°)",
+        }
+    }
+
+    pub fn has_vertical_axis(&self) -> bool {
+        matches!(self, Symmetry::Vertical | Symmetry::Both)
+    }
+
+    pub fn has_horizontal_axis(&self) -> bool {
+        matches!(self, Symmetry::Horizontal | Symmetry::Both)
+    }
+
+    /// The cell followed by its symmetric cells on a grid of the given size,
+    /// without repeats
+    pub fn images(
+        &self,
+        (row, col): (usize, usize),
+        width: usize,
+        height: usize,
+    ) -> Vec<(usize, usize)> {
+        let (mirrored_row, mirrored_col) = (height - 1 - row, width - 1 - col);
+        let images = match self {
+            Symmetry::None => vec![(row, col)],
+            Symmetry::Vertical => vec![(row, col), (row, mirrored_col)],
+            Symmetry::Horizontal => vec![(row, col), (mirrored_row, col)],
+            Symmetry::Both => vec![
+                (row, col),
+                (row, mirrored_col),
+                (mirrored_row, col),
+                (mirrored_row, mirrored_col),
+            ],
+            Symmetry::Rotational => {
+                vec![(row, col), (mirrored_row, mirrored_col)]
+            }
+        };
+        images.into_iter().unique().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_mirror_without_symmetry() {
+        assert_eq!(Symmetry::None.images((1, 2), 5, 5), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn should_mirror_along_axes() {
+        assert_eq!(
+            Symmetry::Vertical.images((1, 0), 5, 4),
+            vec![(1, 0), (1, 4)]
+        );
+        assert_eq!(
+            Symmetry::Horizontal.images((1, 0), 5, 4),
+            vec![(1, 0), (2, 0)]
+        );
+        assert_eq!(
+            Symmetry::Both.images((0, 1), 5, 4),
+            vec![(0, 1), (0, 3), (3, 1), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn should_rotate_around_centre() {
+        assert_eq!(
+            Symmetry::Rotational.images((0, 1), 5, 4),
+            vec![(0, 1), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn should_not_repeat_cells_on_axes() {
+        assert_eq!(Symmetry::Vertical.images((1, 2), 5, 5), vec![(1, 2)]);
+        assert_eq!(Symmetry::Both.images((2, 2), 5, 5), vec![(2, 2)]);
+        assert_eq!(Symmetry::Both.images((0, 2), 5, 5), vec![(0, 2), (4, 2)]);
+    }
+}
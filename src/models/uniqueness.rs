@@ -0,0 +1,296 @@
+//! Suggestions to turn a picture with several solutions into a puzzle with a
+//! unique one, found greedily and therefore small but not always minimal
+
+use super::board::FieldCell;
+use super::clues::Clues;
+use super::grid::Grid;
+use super::solver::{propagate_within, solutions_from, solutions_within, Solutions, SEARCH_BUDGET};
+
+/// How many differing cells are tried when looking for the best next step
+const MAX_CANDIDATES: usize = 12;
+/// Line solves a suggestion may spend on all its searches together, about two
+/// searches' worth to keep the page responsive
+const SUGGESTION_BUDGET: usize = 2 * SEARCH_BUDGET;
+
+/// The search for solutions ran out of its budget
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GaveUp;
+
+/// The givens at the positions revealed in `givens`, showing the cells of
+/// `picture`
+fn revealed(picture: &Grid<FieldCell>, givens: &Grid<FieldCell>) -> Grid<FieldCell> {
+    let mut revealed = givens.clone();
+    for row in 0..revealed.height() {
+        for col in 0..revealed.width() {
+            if revealed[row][col] != FieldCell::Empty {
+                reveal(&mut revealed, picture, (row, col));
+            }
+        }
+    }
+    revealed
+}
+
+fn reveal(givens: &mut Grid<FieldCell>, picture: &Grid<FieldCell>, (row, col): (usize, usize)) {
+    givens[row][col] = match picture[row][col] {
+        FieldCell::Filled => FieldCell::Filled,
+        _ => FieldCell::Marked,
+    };
+}
+
+fn is_unique(
+    picture: &Grid<FieldCell>,
+    givens: &Grid<FieldCell>,
+    budget: &mut usize,
+) -> Result<bool, GaveUp> {
+    let clues = Clues::from_solution(picture);
+    solutions_within(&clues, &revealed(picture, givens), 2, budget)
+        .unique()
+        .ok_or(GaveUp)
+}
+
+/// A solution of the picture's clues extending `start` other than the picture
+/// itself
+fn alternative(
+    clues: &Clues,
+    start: &Grid<FieldCell>,
+    picture: &Grid<FieldCell>,
+    budget: &mut usize,
+) -> Result<Option<Grid<FieldCell>>, GaveUp> {
+    let solutions = solutions_within(clues, start, 2, budget);
+    let other = solutions
+        .found
+        .into_iter()
+        .find(|solution| solution != picture);
+    match other {
+        None if solutions.gave_up => Err(GaveUp),
+        other => Ok(other),
+    }
+}
+
+fn differing_cells(a: &Grid<FieldCell>, b: &Grid<FieldCell>) -> Vec<(usize, usize)> {
+    (0..a.height())
+        .flat_map(|row| (0..a.width()).map(move |col| (row, col)))
+        .filter(|&(row, col)| a[row][col] != b[row][col])
+        .take(MAX_CANDIDATES)
+        .collect()
+}
+
+fn flipped(picture: &Grid<FieldCell>, (row, col): (usize, usize)) -> Grid<FieldCell> {
+    let mut flipped = picture.clone();
+    flipped[row][col] = match picture[row][col] {
+        FieldCell::Filled => FieldCell::Empty,
+        _ => FieldCell::Filled,
+    };
+    flipped
+}
+
+/// Cells the line solver determines starting from the givens, 0 if they
+/// contradict the clues
+fn known_after_propagation(
+    clues: &Clues,
+    givens: &Grid<FieldCell>,
+    budget: &mut usize,
+) -> Result<usize, GaveUp> {
+    let mut grid = givens.clone();
+    match propagate_within(clues, &mut grid, None, budget) {
+        Ok(true) => (),
+        Ok(false) => return Err(GaveUp),
+        Err(_) => return Ok(0),
+    }
+    Ok((0..grid.height())
+        .map(|row| {
+            grid[row]
+                .iter()
+                .filter(|&&cell| cell != FieldCell::Empty)
+                .count()
+        })
+        .sum())
+}
+
+/// Cells, as (row, col), to flip in the picture so that its clues have a unique
+/// solution with the revealed `givens`, None if no such set of at most
+/// `max_flips` cells was found
+pub fn suggest_flips(
+    picture: &Grid<FieldCell>,
+    givens: &Grid<FieldCell>,
+    max_flips: usize,
+) -> Result<Option<Vec<(usize, usize)>>, GaveUp> {
+    let mut budget = SUGGESTION_BUDGET;
+    let mut picture = picture.clone();
+    let mut flips = vec![];
+    loop {
+        let clues = Clues::from_solution(&picture);
+        let start = revealed(&picture, givens);
+        let Some(other) = alternative(&clues, &start, &picture, &mut budget)? else {
+            return Ok(Some(flips));
+        };
+        if flips.len() == max_flips {
+            return Ok(None);
+        }
+        // a flip where the solutions differ breaks the ambiguity between them
+        let candidates = differing_cells(&picture, &other);
+        let mut flip = None;
+        for &cell in &candidates {
+            if is_unique(&flipped(&picture, cell), givens, &mut budget)? {
+                flip = Some(cell);
+                break;
+            }
+        }
+        // failing that, take the one that lets the line solver deduce the most
+        if flip.is_none() {
+            let mut most_known = None;
+            for &cell in &candidates {
+                let candidate = flipped(&picture, cell);
+                let clues = Clues::from_solution(&candidate);
+                let start = revealed(&candidate, givens);
+                let known = known_after_propagation(&clues, &start, &mut budget)?;
+                if most_known.map_or(true, |(most, _)| known > most) {
+                    most_known = Some((known, cell));
+                }
+            }
+            flip = most_known.map(|(_, cell)| cell);
+        }
+        let Some(flip) = flip else {
+            return Ok(None);
+        };
+        picture = flipped(&picture, flip);
+        flips.push(flip);
+    }
+}
+
+/// Up to `limit` solutions of the picture's clues extending `givens` other than
+/// the picture itself, to compare them with it
+pub fn alternative_solutions(
+    picture: &Grid<FieldCell>,
+    givens: &Grid<FieldCell>,
+    limit: usize,
+) -> Solutions {
+    let clues = Clues::from_solution(picture);
+    let solutions = solutions_from(&clues, givens, limit + 1);
+    let found = solutions
+        .found
+        .into_iter()
+        .filter(|solution| solution != picture)
+        .take(limit)
+        .collect();
+    Solutions {
+        found,
+        gave_up: solutions.gave_up,
+    }
+}
+
+/// Cells to reveal at the start so that the picture is the only solution, as a
+/// grid with `Filled` for revealed filled cells, `Marked` for revealed empty
+/// cells and `Empty` elsewhere. The cells revealed in `givens` stay revealed
+pub fn suggest_givens(
+    picture: &Grid<FieldCell>,
+    givens: &Grid<FieldCell>,
+) -> Result<Grid<FieldCell>, GaveUp> {
+    let mut budget = SUGGESTION_BUDGET;
+    let clues = Clues::from_solution(picture);
+    let kept = revealed(picture, givens);
+    let mut givens = kept.clone();
+    while let Some(other) = alternative(&clues, &givens, picture, &mut budget)? {
+        // reveal the differing cell that lets the line solver deduce the most
+        let mut most_known = None;
+        for cell in differing_cells(picture, &other) {
+            let mut candidate = givens.clone();
+            reveal(&mut candidate, picture, cell);
+            let known = known_after_propagation(&clues, &candidate, &mut budget)?;
+            if most_known.map_or(true, |(most, _)| known >= most) {
+                most_known = Some((known, cell));
+            }
+        }
+        let (_, best) = most_known.expect("Different solutions differ in at least one cell");
+        reveal(&mut givens, picture, best);
+    }
+    // drop added givens that turned out to be unnecessary
+    for row in 0..givens.height() {
+        for col in 0..givens.width() {
+            let given = givens[row][col];
+            if given == FieldCell::Empty || kept[row][col] != FieldCell::Empty {
+                continue;
+            }
+            givens[row][col] = FieldCell::Empty;
+            if !matches!(alternative(&clues, &givens, picture, &mut budget), Ok(None)) {
+                givens[row][col] = given;
+            }
+        }
+    }
+    Ok(givens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use FieldCell::{Empty as U, Filled as F, Marked as X};
+
+    #[test]
+    fn should_not_suggest_anything_for_unique_picture() {
+        let picture = Grid::from_flat(3, &[F, U, F, F, F, F, U, F, U]);
+        let none = Grid::new(3, 3);
+        assert_eq!(suggest_flips(&picture, &none, 3), Ok(Some(vec![])));
+        assert_eq!(suggest_givens(&picture, &none), Ok(none));
+    }
+
+    #[test]
+    fn should_suggest_flip_making_diagonal_unique() {
+        let picture = Grid::from_flat(2, &[F, U, U, F]);
+        let none = Grid::new(2, 2);
+        let flips = suggest_flips(&picture, &none, 3).unwrap().unwrap();
+        assert_eq!(flips.len(), 1);
+        let flipped = flipped(&picture, flips[0]);
+        let mut budget = SEARCH_BUDGET;
+        assert_eq!(is_unique(&flipped, &none, &mut budget), Ok(true));
+    }
+
+    #[test]
+    fn should_give_up_when_flips_do_not_suffice() {
+        let picture = Grid::from_flat(2, &[F, U, U, F]);
+        assert_eq!(suggest_flips(&picture, &Grid::new(2, 2), 0), Ok(None));
+    }
+
+    #[test]
+    fn should_list_other_solutions_of_diagonal() {
+        let picture = Grid::from_flat(2, &[F, U, U, F]);
+        let others = alternative_solutions(&picture, &Grid::new(2, 2), 5);
+        assert_eq!(others.found, vec![Grid::from_flat(2, &[U, F, F, U])]);
+        let givens = Grid::from_flat(2, &[F, U, U, U]);
+        assert!(alternative_solutions(&picture, &givens, 5).found.is_empty());
+    }
+
+    #[test]
+    fn should_suggest_single_given_for_diagonal() {
+        let picture = Grid::from_flat(2, &[F, U, U, F]);
+        let givens = suggest_givens(&picture, &Grid::new(2, 2)).unwrap();
+        let revealed = (0..2)
+            .flat_map(|row| (0..2).map(move |col| (row, col)))
+            .filter(|&(row, col)| givens[row][col] != U)
+            .collect::<Vec<_>>();
+        assert_eq!(revealed.len(), 1);
+        let (row, col) = revealed[0];
+        let expected = if picture[row][col] == F { F } else { X };
+        assert_eq!(givens[row][col], expected);
+        let clues = Clues::from_solution(&picture);
+        assert_eq!(solutions_from(&clues, &givens, 2).found, vec![picture]);
+    }
+
+    #[test]
+    fn should_keep_existing_givens_when_suggesting() {
+        let picture = Grid::from_flat(3, &[F, U, U, U, F, U, U, U, F]);
+        let existing = Grid::from_flat(3, &[U, U, U, U, U, U, U, U, F]);
+        let givens = suggest_givens(&picture, &existing).unwrap();
+        assert_eq!(givens[2][2], F);
+        let clues = Clues::from_solution(&picture);
+        assert_eq!(
+            solutions_from(&clues, &givens, 2).found,
+            vec![picture.clone()]
+        );
+        let flips = suggest_flips(&picture, &existing, 3).unwrap().unwrap();
+        let flipped = flips
+            .iter()
+            .fold(picture, |picture, &cell| flipped(&picture, cell));
+        let mut budget = SEARCH_BUDGET;
+        assert_eq!(is_unique(&flipped, &existing, &mut budget), Ok(true));
+    }
+}
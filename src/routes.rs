@@ -1,3 +1,5 @@
+use wasm_bindgen::JsCast;
+use web_sys::HtmlAnchorElement;
 use yew_router::prelude::*;
 
 #[derive(Clone, PartialEq, Routable)]
@@ -6,8 +8,36 @@ pub enum Route {
     Home,
     #[at("/solve/:puzzle")]
     Solve { puzzle: String },
+    #[at("/solve/:puzzle/lives/:lives")]
+    SolveChallenge { puzzle: String, lives: u32 },
     #[at("/set/:puzzle")]
     Set { puzzle: String },
+    #[at("/set/:puzzle/draft/:draft")]
+    SetDraft { puzzle: String, draft: u32 },
     #[at("/set")]
     SetNew,
+    #[at("/clues")]
+    Clues,
+    #[at("/print/:puzzles")]
+    Print { puzzles: String },
+    #[at("/library")]
+    Library,
+}
+
+/// 'Absolute' URI of a route, to share outside the app
+pub fn to_href(navigator: &Navigator, route: &Route) -> String {
+    let url = match navigator.basename() {
+        Some(base) => format!("{}{}", base, route.to_path()),
+        None => route.to_path(),
+    };
+    // the anchor resolves the URL against the current location
+    let anchor = web_sys::window()
+        .expect("Could not get window")
+        .document()
+        .expect("Could not get document")
+        .create_element("a")
+        .expect("Could not create anchor")
+        .unchecked_into::<HtmlAnchorElement>();
+    anchor.set_href(&url);
+    anchor.href()
 }
@@ -0,0 +1,24 @@
+//! Values kept in the browser's local storage, only on this device
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+/// Returns false if the value could not be stored, e.g. when it is too large
+pub fn store(key: &str, value: &str) -> bool {
+    let stored = local_storage().is_some_and(|storage| storage.set_item(key, value).is_ok());
+    if !stored {
+        log::warn!("Could not store '{key}' in local storage");
+    }
+    stored
+}
+
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}